@@ -0,0 +1,889 @@
+//! front end of the brainfuck macros
+//!
+//! the program is parsed once and expanded in a single step, unlike a `macro_rules!` tt muncher
+//! the macro recursion depth does not grow with the length of the program
+//!
+//! the macros building types are called through the `macro_rules!` wrappers of the same name in
+//! the brainfuck crate, which pass `$crate` first so every emitted path is absolute
+use proc_macro::{Delimiter, Group, Ident, Literal, Punct, Spacing, Span, TokenStream, TokenTree};
+
+use parse::{Command, Error};
+
+mod parse {
+    use proc_macro::{Delimiter, Group, Span, TokenStream, TokenTree};
+
+    #[derive(Clone)]
+    pub enum Command {
+        Right,
+        Left,
+        Incr,
+        Decr,
+        Print,
+        Read,
+        Loop(Vec<Command>),
+        /// only produced by `optimize::offsets`
+        IncrAt(usize),
+        DecrAt(usize),
+        /// a snippet type in parentheses like `(MoveTo<Two>)`, spliced in by `Splice`
+        Splice(Group),
+    }
+    impl Command {
+        /// recognize scan loops like `[>]` or `[<<]`, returns the direction and stride
+        pub fn scan(&self) -> Option<(&'static str, usize)> {
+            match self {
+                Command::Loop(body) if !body.is_empty() => {
+                    if body.iter().all(|c| matches!(c, Command::Right)) {
+                        Some(("ScanRight", body.len()))
+                    } else if body.iter().all(|c| matches!(c, Command::Left)) {
+                        Some(("ScanLeft", body.len()))
+                    } else {
+                        None
+                    }
+                }
+                _ => None,
+            }
+        }
+        /// the amount of commands including those in the body of a loop
+        pub fn len(&self) -> usize {
+            match self {
+                Command::Loop(body) => 1 + body.iter().map(Command::len).sum::<usize>(),
+                _ => 1,
+            }
+        }
+    }
+
+    pub struct Error {
+        pub span: Span,
+        pub message: String,
+    }
+
+    /// split the macro input at the first top level `;` into whatever comes before and the program
+    pub fn split(input: TokenStream) -> (TokenStream, TokenStream) {
+        let mut tokens = input.into_iter();
+        let head = tokens
+            .by_ref()
+            .take_while(|token| !matches!(token, TokenTree::Punct(p) if p.as_char() == ';'))
+            .collect();
+        (head, tokens.collect())
+    }
+
+    /// parse a program, `name` is used in the error messages and snippets in parentheses are
+    /// only accepted with `allow_splice`
+    pub fn parse(
+        name: &str,
+        allow_splice: bool,
+        program: TokenStream,
+    ) -> Result<Vec<Command>, Error> {
+        let mut parser = Parser {
+            name,
+            allow_splice,
+            position: 0,
+        };
+        parser.parse(program)
+    }
+
+    struct Parser<'a> {
+        name: &'a str,
+        allow_splice: bool,
+        /// index of the current command in the program, counting both brackets of a loop
+        position: usize,
+    }
+    impl Parser<'_> {
+        fn parse(&mut self, program: TokenStream) -> Result<Vec<Command>, Error> {
+            let mut commands = Vec::new();
+            for token in program {
+                match &token {
+                    TokenTree::Punct(p) => commands.push(match p.as_char() {
+                        '>' => Command::Right,
+                        '<' => Command::Left,
+                        '+' => Command::Incr,
+                        '-' => Command::Decr,
+                        '.' => Command::Print,
+                        ',' => Command::Read,
+                        _ => return Err(self.error(&token, "unknown token")),
+                    }),
+                    TokenTree::Group(g) if g.delimiter() == Delimiter::Bracket => {
+                        self.position += 1;
+                        commands.push(Command::Loop(self.parse(g.stream())?));
+                    }
+                    TokenTree::Group(g)
+                        if g.delimiter() == Delimiter::Parenthesis && self.allow_splice =>
+                    {
+                        if g.stream().is_empty() {
+                            return Err(self.error(&token, "expected a snippet type"));
+                        }
+                        commands.push(Command::Splice(g.clone()))
+                    }
+                    // tokens forwarded by a macro_rules! macro may come wrapped in an invisible group
+                    TokenTree::Group(g) if g.delimiter() == Delimiter::None => {
+                        commands.extend(self.parse(g.stream())?);
+                        continue;
+                    }
+                    _ => return Err(self.error(&token, "unknown token")),
+                }
+                self.position += 1;
+            }
+            Ok(commands)
+        }
+        fn error(&self, token: &TokenTree, message: &str) -> Error {
+            Error {
+                span: token.span(),
+                message: format!(
+                    "{}!: {message}, found `{token}` at position {}",
+                    self.name, self.position
+                ),
+            }
+        }
+    }
+}
+
+mod optimize {
+    use proc_macro::{Delimiter, TokenStream, TokenTree};
+
+    use crate::parse::Command;
+
+    /// peephole pass over the parsed program
+    ///
+    /// - `+-` and `><` cancel out, `-+` and `<>` do not as decrementing 0 and moving left of
+    ///   cell 0 saturate, `><` on the last cell of a strict tape is dropped rather than rejected
+    /// - a loop directly after another loop never runs as the cell is 0 once the first one ends
+    /// - with `fresh` set a loop before anything changed the state never runs either, as the
+    ///   program starts on a zero cell
+    pub fn peephole(commands: Vec<Command>, fresh: bool) -> Vec<Command> {
+        pass(commands, fresh)
+    }
+
+    /// whether `state` is literally an `Interpreter` or an `InterpreterBase` on an empty tape,
+    /// anything else like an alias or an executed program may start on a non-zero cell
+    pub fn fresh(state: TokenStream) -> bool {
+        let mut tokens = state.into_iter().peekable();
+        // a `$state:ty` passed on by a `macro_rules!` macro arrives as an invisible group
+        if let Some(TokenTree::Group(g)) = tokens.peek() {
+            if g.delimiter() == Delimiter::None {
+                return fresh(g.stream());
+            }
+        }
+        let mut name = None;
+        while let Some(token) = tokens.next_if(|token| match token {
+            TokenTree::Ident(_) => true,
+            TokenTree::Punct(p) => p.as_char() == ':',
+            _ => false,
+        }) {
+            if let TokenTree::Ident(i) = token {
+                name = Some(i.to_string());
+            }
+        }
+        let generics = match tokens.next() {
+            None => vec![],
+            Some(TokenTree::Punct(p)) if p.as_char() == '<' => tokens.collect(),
+            Some(_) => return false,
+        };
+        match name.as_deref() {
+            Some("Interpreter") => true,
+            // the tape is the first argument, `EmptyIndexed` or `list![]`
+            Some("InterpreterBase") => {
+                let tape: Vec<_> = generics
+                    .iter()
+                    .take_while(|token| !matches!(token, TokenTree::Punct(p) if p.as_char() == ','))
+                    .map(ToString::to_string)
+                    .collect();
+                tape.last().is_some_and(|last| last == "EmptyIndexed")
+                    || tape == ["list", "!", "[]"]
+                    || tape == ["list", "!", "()"]
+            }
+            _ => false,
+        }
+    }
+
+    /// rewrite runs of `<>+-` that end up where they started into `IncrAt`/`DecrAt` so the
+    /// pointer does not have to move, runs moving left of their start are kept as moving left
+    /// of cell 0 saturates
+    pub fn offsets(commands: Vec<Command>) -> Vec<Command> {
+        let mut out = Vec::new();
+        let mut run = Vec::new();
+        for command in commands {
+            match command {
+                Command::Right | Command::Left | Command::Incr | Command::Decr => run.push(command),
+                command => {
+                    flush(&mut run, &mut out);
+                    out.push(match command {
+                        Command::Loop(body) => Command::Loop(offsets(body)),
+                        command => command,
+                    });
+                }
+            }
+        }
+        flush(&mut run, &mut out);
+        out
+    }
+    fn flush(run: &mut Vec<Command>, out: &mut Vec<Command>) {
+        let mut offset = 0isize;
+        let mut min = 0;
+        let mut rewritten = Vec::new();
+        for command in run.iter() {
+            match (command, offset) {
+                (Command::Right, _) => offset += 1,
+                (Command::Left, _) => offset -= 1,
+                (Command::Incr, 0) => rewritten.push(Command::Incr),
+                (Command::Decr, 0) => rewritten.push(Command::Decr),
+                (Command::Incr, _) => rewritten.push(Command::IncrAt(offset as usize)),
+                (Command::Decr, _) => rewritten.push(Command::DecrAt(offset as usize)),
+                _ => unreachable!("runs only hold moves and increments"),
+            }
+            min = min.min(offset);
+        }
+        if offset == 0 && min == 0 {
+            out.append(&mut rewritten);
+            run.clear();
+        } else {
+            out.append(run);
+        }
+    }
+
+    /// `start` is set while everything before `commands` left the initial state unchanged
+    fn pass(commands: Vec<Command>, start: bool) -> Vec<Command> {
+        let mut out: Vec<Command> = Vec::new();
+        for command in commands {
+            match (out.last(), &command) {
+                (Some(Command::Incr), Command::Decr) | (Some(Command::Right), Command::Left) => {
+                    out.pop();
+                    continue;
+                }
+                (Some(Command::Loop(_)), Command::Loop(_)) => continue,
+                (None, Command::Loop(_)) if start => continue,
+                _ => {}
+            }
+            out.push(match command {
+                Command::Loop(body) => Command::Loop(pass(body, false)),
+                command => command,
+            });
+        }
+        out
+    }
+}
+
+mod lang {
+    use proc_macro::{Delimiter, Span, TokenStream, TokenTree};
+    use std::collections::{HashMap, HashSet};
+
+    use crate::parse::{Command, Error};
+
+    enum Statement {
+        Let(Cell, usize),
+        Add(Cell, Operand),
+        Sub(Cell, Operand),
+        Copy(Cell, Cell),
+        Clear(Cell),
+        Print(Cell),
+        While(Cell, Vec<Statement>),
+    }
+    enum Operand {
+        Value(usize),
+        Cell(Cell),
+    }
+    struct Cell {
+        name: String,
+        span: Span,
+    }
+
+    /// parse and lower a program, every named cell gets its own tape cell in the order they are
+    /// declared and the cell after them is scratch space for `add`, `sub` and `copy`
+    pub fn compile(program: TokenStream) -> Result<Vec<Command>, Error> {
+        let statements = parse(program)?;
+        let mut cells = HashMap::new();
+        declare(&statements, &mut cells)?;
+        let mut lowering = Lowering {
+            scratch: cells.len(),
+            cells,
+            declared: HashSet::new(),
+            pointer: 0,
+        };
+        let mut commands = Vec::new();
+        lowering.lower(&statements, false, &mut commands)?;
+        Ok(commands)
+    }
+
+    fn error(span: Span, message: &str) -> Error {
+        Error {
+            span,
+            message: format!("bf_lang!: {message}"),
+        }
+    }
+
+    fn parse(program: TokenStream) -> Result<Vec<Statement>, Error> {
+        let mut statements = Vec::new();
+        let mut tokens = program.into_iter();
+        while let Some(token) = tokens.next() {
+            let TokenTree::Ident(keyword) = &token else {
+                return Err(error(
+                    token.span(),
+                    &format!("expected a statement, found `{token}`"),
+                ));
+            };
+            let keyword_name = keyword.to_string();
+            let mut arguments = Vec::new();
+            // everything up to the `;`, or the body of a `while`
+            let mut body = None;
+            for token in tokens.by_ref() {
+                match token {
+                    TokenTree::Punct(p) if p.as_char() == ';' => break,
+                    TokenTree::Group(g)
+                        if g.delimiter() == Delimiter::Brace && keyword_name == "while" =>
+                    {
+                        body = Some(g);
+                        break;
+                    }
+                    token => arguments.push(token),
+                }
+            }
+            let cell = |i: usize| match arguments.get(i) {
+                Some(TokenTree::Ident(name)) => Ok(Cell {
+                    name: name.to_string(),
+                    span: name.span(),
+                }),
+                Some(token) => Err(error(
+                    token.span(),
+                    &format!("expected a cell, found `{token}`"),
+                )),
+                None => Err(error(
+                    keyword.span(),
+                    &format!("`{keyword}` is missing a cell"),
+                )),
+            };
+            let operand = |i: usize| match arguments.get(i) {
+                Some(TokenTree::Literal(literal)) => literal
+                    .to_string()
+                    .parse()
+                    .map(Operand::Value)
+                    .map_err(|_| error(literal.span(), "expected a number or a cell")),
+                _ => cell(i).map(Operand::Cell),
+            };
+            let separated = |expected: usize| {
+                let commas = arguments
+                    .iter()
+                    .skip(1)
+                    .step_by(2)
+                    .all(|token| matches!(token, TokenTree::Punct(p) if p.as_char() == ','));
+                if arguments.len() == 2 * expected - 1 && commas {
+                    Ok(())
+                } else {
+                    Err(error(
+                        keyword.span(),
+                        &format!("`{keyword}` takes {expected} comma separated arguments"),
+                    ))
+                }
+            };
+            statements.push(match keyword_name.as_str() {
+                "let" => match arguments.as_slice() {
+                    [_] => Statement::Let(cell(0)?, 0),
+                    [_, TokenTree::Punct(p), _] if p.as_char() == '=' => match operand(2)? {
+                        Operand::Value(value) => Statement::Let(cell(0)?, value),
+                        Operand::Cell(cell) => {
+                            return Err(error(cell.span, "a cell starts as a number"))
+                        }
+                    },
+                    _ => {
+                        return Err(error(
+                            keyword.span(),
+                            "expected `let name;` or `let name = 3;`",
+                        ))
+                    }
+                },
+                "add" => {
+                    separated(2)?;
+                    Statement::Add(cell(0)?, operand(2)?)
+                }
+                "sub" => {
+                    separated(2)?;
+                    Statement::Sub(cell(0)?, operand(2)?)
+                }
+                "copy" => {
+                    separated(2)?;
+                    Statement::Copy(cell(0)?, cell(2)?)
+                }
+                "clear" => {
+                    separated(1)?;
+                    Statement::Clear(cell(0)?)
+                }
+                "print" => {
+                    separated(1)?;
+                    Statement::Print(cell(0)?)
+                }
+                "while" => {
+                    separated(1)?;
+                    let Some(body) = body else {
+                        return Err(error(keyword.span(), "`while` is missing its body"));
+                    };
+                    Statement::While(cell(0)?, parse(body.stream())?)
+                }
+                _ => {
+                    return Err(error(
+                        keyword.span(),
+                        &format!("unknown statement `{keyword}`"),
+                    ))
+                }
+            });
+        }
+        Ok(statements)
+    }
+
+    /// give every `let` the next free cell, whether a cell is used after its `let` is checked
+    /// while lowering
+    fn declare(statements: &[Statement], cells: &mut HashMap<String, usize>) -> Result<(), Error> {
+        for statement in statements {
+            match statement {
+                Statement::Let(cell, _) => {
+                    if cells.contains_key(&cell.name) {
+                        return Err(error(
+                            cell.span,
+                            &format!("`{}` is already declared", cell.name),
+                        ));
+                    }
+                    cells.insert(cell.name.clone(), cells.len());
+                }
+                Statement::While(_, body) => declare(body, cells)?,
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    struct Lowering {
+        cells: HashMap<String, usize>,
+        /// the cells whose `let` was already lowered
+        declared: HashSet<String>,
+        scratch: usize,
+        /// where the pointer is while the program runs, every statement leaves it somewhere
+        /// known so a loop body is lowered to end where it started
+        pointer: usize,
+    }
+    impl Lowering {
+        fn lower(
+            &mut self,
+            statements: &[Statement],
+            in_loop: bool,
+            out: &mut Vec<Command>,
+        ) -> Result<(), Error> {
+            for statement in statements {
+                match statement {
+                    Statement::Let(cell, value) => {
+                        self.declared.insert(cell.name.clone());
+                        let cell = self.cell(cell)?;
+                        // a fresh cell is zero, one declared in a loop holds the last iteration
+                        if in_loop {
+                            self.clear(cell, out);
+                        }
+                        self.add(cell, *value, Command::Incr, out);
+                    }
+                    Statement::Add(cell, operand) | Statement::Sub(cell, operand) => {
+                        let step = match statement {
+                            Statement::Add(..) => Command::Incr,
+                            _ => Command::Decr,
+                        };
+                        let target = self.cell(cell)?;
+                        match operand {
+                            Operand::Value(value) => self.add(target, *value, step, out),
+                            Operand::Cell(source) => {
+                                let source = self.source(target, source)?;
+                                self.add_cell(target, source, step, out);
+                            }
+                        }
+                    }
+                    Statement::Copy(cell, source) => {
+                        let target = self.cell(cell)?;
+                        let source = self.source(target, source)?;
+                        self.clear(target, out);
+                        self.add_cell(target, source, Command::Incr, out);
+                    }
+                    Statement::Clear(cell) => {
+                        let cell = self.cell(cell)?;
+                        self.clear(cell, out);
+                    }
+                    Statement::Print(cell) => {
+                        let cell = self.cell(cell)?;
+                        self.goto(cell, out);
+                        out.push(Command::Print);
+                    }
+                    Statement::While(cell, body) => {
+                        let cell = self.cell(cell)?;
+                        self.goto(cell, out);
+                        let mut commands = Vec::new();
+                        self.lower(body, true, &mut commands)?;
+                        self.goto(cell, &mut commands);
+                        out.push(Command::Loop(commands));
+                    }
+                }
+            }
+            Ok(())
+        }
+        fn cell(&self, cell: &Cell) -> Result<usize, Error> {
+            match self.cells.get(&cell.name) {
+                Some(_) if !self.declared.contains(&cell.name) => Err(error(
+                    cell.span,
+                    &format!("`{}` used before `let`", cell.name),
+                )),
+                Some(&index) => Ok(index),
+                None => Err(error(
+                    cell.span,
+                    &format!("`{}` is not declared", cell.name),
+                )),
+            }
+        }
+        /// the cell read by `add`, `sub` or `copy` into `target`
+        fn source(&self, target: usize, source: &Cell) -> Result<usize, Error> {
+            match self.cell(source)? {
+                cell if cell == target => Err(error(
+                    source.span,
+                    &format!("`{}` is read and written at once", source.name),
+                )),
+                cell => Ok(cell),
+            }
+        }
+        fn goto(&mut self, cell: usize, out: &mut Vec<Command>) {
+            while self.pointer < cell {
+                out.push(Command::Right);
+                self.pointer += 1;
+            }
+            while self.pointer > cell {
+                out.push(Command::Left);
+                self.pointer -= 1;
+            }
+        }
+        fn add(&mut self, cell: usize, value: usize, step: Command, out: &mut Vec<Command>) {
+            // no need to walk over to a cell that stays the same
+            if value > 0 {
+                self.goto(cell, out);
+                out.extend((0..value).map(|_| step.clone()));
+            }
+        }
+        fn clear(&mut self, cell: usize, out: &mut Vec<Command>) {
+            self.goto(cell, out);
+            out.push(Command::Loop(vec![Command::Decr]));
+        }
+        /// `target += source` or `target -= source`, `source` is moved into the scratch cell and
+        /// back again
+        fn add_cell(
+            &mut self,
+            target: usize,
+            source: usize,
+            step: Command,
+            out: &mut Vec<Command>,
+        ) {
+            self.goto(source, out);
+            let mut body = vec![Command::Decr];
+            self.add(target, 1, step, &mut body);
+            self.add(self.scratch, 1, Command::Incr, &mut body);
+            self.goto(source, &mut body);
+            out.push(Command::Loop(body));
+
+            self.goto(self.scratch, out);
+            let mut body = vec![Command::Decr];
+            self.add(source, 1, Command::Incr, &mut body);
+            self.goto(self.scratch, &mut body);
+            out.push(Command::Loop(body));
+        }
+    }
+}
+
+impl Error {
+    fn into_compile_error(self) -> TokenStream {
+        let mut message = Literal::string(&self.message);
+        message.set_span(self.span);
+        let mut bang = Punct::new('!', Spacing::Alone);
+        bang.set_span(self.span);
+        let mut args = Group::new(Delimiter::Parenthesis, TokenTree::from(message).into());
+        args.set_span(self.span);
+        TokenStream::from_iter([
+            TokenTree::from(Ident::new("compile_error", self.span)),
+            bang.into(),
+            args.into(),
+        ])
+    }
+}
+
+/// `krate::module::name`, `krate` is the path to the brainfuck crate
+fn path(krate: &TokenStream, module: &str, name: &str, tokens: &mut Vec<TokenTree>) {
+    tokens.extend(krate.clone());
+    for segment in [module, name] {
+        tokens.push(Punct::new(':', Spacing::Joint).into());
+        tokens.push(Punct::new(':', Spacing::Alone).into());
+        tokens.push(Ident::new(segment, Span::call_site()).into());
+    }
+}
+
+fn open(krate: &TokenStream, name: &str, tokens: &mut Vec<TokenTree>) {
+    path(krate, "instructions", name, tokens);
+    tokens.push(Punct::new('<', Spacing::Alone).into());
+}
+
+/// write the instruction chain of `commands` up to where its base goes, the chain is built
+/// inside out so the last command ends up first, returns the amount of `<` left open
+fn open_chain(krate: &TokenStream, commands: &[Command], tokens: &mut Vec<TokenTree>) -> usize {
+    let mut depth = 0;
+    for command in commands.iter().rev() {
+        // instructions like `ScanRight<Stride, T>` take a type before the rest of the chain
+        let (name, argument) = match command {
+            Command::Right => ("MoveRight", None),
+            Command::Left => ("MoveLeft", None),
+            Command::Incr => ("Incr", None),
+            Command::Decr => ("Decr", None),
+            Command::Print => ("Print", None),
+            Command::Read => ("Read", None),
+            Command::IncrAt(offset) => ("IncrAt", Some(number(krate, *offset))),
+            Command::DecrAt(offset) => ("DecrAt", Some(number(krate, *offset))),
+            Command::Splice(snippet) => ("Splice", Some(snippet.stream())),
+            Command::Loop(body) => match command.scan() {
+                Some((name, stride)) => (name, Some(number(krate, stride))),
+                None => {
+                    open(krate, "LoopEnd", tokens);
+                    depth += 1 + open_chain(krate, body, tokens);
+                    ("LoopStart", None)
+                }
+            },
+        };
+        open(krate, name, tokens);
+        if let Some(argument) = argument {
+            tokens.extend(argument);
+            tokens.push(Punct::new(',', Spacing::Alone).into());
+        }
+        depth += 1;
+    }
+    depth
+}
+
+/// the type level number `n`
+fn number(krate: &TokenStream, n: usize) -> TokenStream {
+    let mut tokens = Vec::new();
+    for _ in 0..n {
+        path(krate, "numbers", "NextNumber", &mut tokens);
+        tokens.push(Punct::new('<', Spacing::Alone).into());
+    }
+    path(krate, "numbers", "Zero", &mut tokens);
+    close(n, &mut tokens);
+    tokens.into_iter().collect()
+}
+
+fn close(depth: usize, tokens: &mut Vec<TokenTree>) {
+    tokens.extend((0..depth).map(|_| TokenTree::from(Punct::new('>', Spacing::Alone))));
+}
+
+/// the instruction chain of `commands` running on `ram`
+fn chain(krate: &TokenStream, commands: &[Command], ram: TokenStream) -> TokenStream {
+    let mut tokens = Vec::new();
+    let depth = open_chain(krate, commands, &mut tokens);
+    tokens.extend(ram);
+    close(depth, &mut tokens);
+    tokens.into_iter().collect()
+}
+
+/// expands `bf!`, the input is `krate; state; program`
+#[proc_macro]
+pub fn bf(input: TokenStream) -> TokenStream {
+    let (krate, input) = parse::split(input);
+    let (ram, program) = parse::split(input);
+    match parse::parse("bf", true, program) {
+        Ok(commands) => chain(&krate, &commands, ram),
+        Err(e) => e.into_compile_error(),
+    }
+}
+
+/// expands `bf_opt!`, the input is `krate; state; program`
+#[proc_macro]
+pub fn bf_opt(input: TokenStream) -> TokenStream {
+    let (krate, input) = parse::split(input);
+    let (ram, program) = parse::split(input);
+    let fresh = optimize::fresh(ram.clone());
+    match parse::parse("bf_opt", true, program) {
+        Ok(commands) => chain(
+            &krate,
+            &optimize::offsets(optimize::peephole(commands, fresh)),
+            ram,
+        ),
+        Err(e) => e.into_compile_error(),
+    }
+}
+
+/// expands `bf_lang!`, the input is `krate; state; program`
+#[proc_macro]
+pub fn bf_lang(input: TokenStream) -> TokenStream {
+    let (krate, input) = parse::split(input);
+    let (ram, program) = parse::split(input);
+    match lang::compile(program) {
+        Ok(commands) => chain(&krate, &commands, ram),
+        Err(e) => e.into_compile_error(),
+    }
+}
+
+/// commands in a chunk of `bf_chunked!` after which it is split even without a loop
+const CHUNK_LEN: usize = 64;
+
+/// expands `bf_chunked!`, the input is `krate; state; program` for the final state as a type or
+/// `krate; type Name = state; program` for a type alias per chunk
+#[proc_macro]
+pub fn bf_chunked(input: TokenStream) -> TokenStream {
+    let (krate, input) = parse::split(input);
+    let (head, program) = parse::split(input);
+    let commands = match parse::parse("bf_chunked", true, program) {
+        Ok(commands) => commands,
+        Err(e) => return e.into_compile_error(),
+    };
+    let mut chunks = vec![];
+    let (mut start, mut len) = (0, 0);
+    for (i, command) in commands.iter().enumerate() {
+        len += command.len();
+        if matches!(command, Command::Loop(_)) || len >= CHUNK_LEN || i + 1 == commands.len() {
+            chunks.push(&commands[start..=i]);
+            (start, len) = (i + 1, 0);
+        }
+    }
+    if chunks.is_empty() {
+        chunks.push(&[]);
+    }
+
+    // `type` can not appear in a type, so it tells the two forms apart
+    let is_type =
+        |token: &TokenTree| matches!(token, TokenTree::Ident(i) if i.to_string() == "type");
+    if !head.clone().into_iter().any(|token| is_type(&token)) {
+        let mut state: Vec<TokenTree> = head.into_iter().collect();
+        for chunk in chunks {
+            let mut tokens = Vec::new();
+            open(&krate, "Execute", &mut tokens);
+            let depth = 1 + open_chain(&krate, chunk, &mut tokens);
+            tokens.append(&mut state);
+            close(depth, &mut tokens);
+            state = tokens;
+        }
+        return state.into_iter().collect();
+    }
+
+    // everything up to `type` is passed on to every alias, like the visibility
+    let mut head = head.into_iter();
+    let attributes: Vec<_> = head.by_ref().take_while(|token| !is_type(token)).collect();
+    let (Some(TokenTree::Ident(name)), Some(TokenTree::Punct(eq))) = (head.next(), head.next())
+    else {
+        return Error {
+            span: Span::call_site(),
+            message: "bf_chunked!: expected `type Name = State; program`".to_owned(),
+        }
+        .into_compile_error();
+    };
+    if eq.as_char() != '=' {
+        return Error {
+            span: eq.span(),
+            message: "bf_chunked!: expected `=`".to_owned(),
+        }
+        .into_compile_error();
+    }
+
+    let mut tokens = Vec::new();
+    let mut state: Vec<TokenTree> = head.collect();
+    for (i, chunk) in chunks.iter().enumerate() {
+        let alias = if i + 1 == chunks.len() {
+            name.clone()
+        } else {
+            Ident::new(&format!("{name}Chunk{i}"), name.span())
+        };
+        tokens.extend(attributes.iter().cloned());
+        tokens.push(Ident::new("type", Span::call_site()).into());
+        tokens.push(alias.clone().into());
+        tokens.push(Punct::new('=', Spacing::Alone).into());
+        open(&krate, "Execute", &mut tokens);
+        let depth = 1 + open_chain(&krate, chunk, &mut tokens);
+        tokens.append(&mut state);
+        close(depth, &mut tokens);
+        tokens.push(Punct::new(';', Spacing::Alone).into());
+        state.push(alias.into());
+    }
+    tokens.into_iter().collect()
+}
+
+/// write the native code of `commands`, runs of the same command are folded into one statement
+fn native(commands: &[Command], code: &mut String) {
+    let mut i = 0;
+    while i < commands.len() {
+        let run = commands[i..]
+            .iter()
+            .take_while(|command| {
+                std::mem::discriminant(*command) == std::mem::discriminant(&commands[i])
+            })
+            .count();
+        match &commands[i] {
+            Command::Right => code.push_str(&format!("ptr += {run};")),
+            Command::Left => code.push_str(&format!("ptr -= {run};")),
+            Command::Incr => code.push_str(&format!(
+                "tape[ptr] = tape[ptr].wrapping_add({});",
+                run as u8
+            )),
+            Command::Decr => code.push_str(&format!(
+                "tape[ptr] = tape[ptr].wrapping_sub({});",
+                run as u8
+            )),
+            Command::Print => {
+                for _ in 0..run {
+                    code.push_str("output.write_all(&[tape[ptr]])?;");
+                }
+            }
+            Command::Read => {
+                for _ in 0..run {
+                    code.push_str(
+                        "let mut byte = [0u8];
+                        tape[ptr] = if input.read(&mut byte)? == 0 { 0 } else { byte[0] };",
+                    );
+                }
+            }
+            Command::IncrAt(offset) => {
+                code.push_str(&format!(
+                    "tape[ptr + {offset}] = tape[ptr + {offset}].wrapping_add(1);"
+                ));
+                i += 1;
+                continue;
+            }
+            Command::DecrAt(offset) => {
+                code.push_str(&format!(
+                    "tape[ptr + {offset}] = tape[ptr + {offset}].wrapping_sub(1);"
+                ));
+                i += 1;
+                continue;
+            }
+            Command::Loop(body) => {
+                code.push_str("while tape[ptr] != 0 {");
+                native(body, code);
+                code.push('}');
+                i += 1;
+                continue;
+            }
+            Command::Splice(_) => unreachable!("`bf_fn!` does not parse snippets"),
+        }
+        i += run;
+    }
+}
+
+/// expands `bf_fn!`, the input is `vis fn name; program`
+#[proc_macro]
+pub fn bf_fn(input: TokenStream) -> TokenStream {
+    let (head, program) = parse::split(input);
+    // snippets are types, there is nothing to splice into native code
+    let commands = match parse::parse("bf_fn", false, program) {
+        Ok(commands) => commands,
+        Err(e) => {
+            let mut error = e.into_compile_error();
+            error.extend([TokenTree::from(Punct::new(';', Spacing::Alone))]);
+            return error;
+        }
+    };
+    let mut code = String::from(
+        "(
+            input: &mut impl ::std::io::Read,
+            output: &mut impl ::std::io::Write,
+        ) -> ::std::io::Result<()> {
+            let mut tape = [0u8; 30000];
+            let mut ptr = 0usize;",
+    );
+    native(&commands, &mut code);
+    code.push_str("Ok(()) }");
+
+    let mut tokens: TokenStream = "#[allow(unused_mut, unused_variables)]".parse().unwrap();
+    tokens.extend(head);
+    tokens.extend(code.parse::<TokenStream>().unwrap());
+    tokens
+}
@@ -0,0 +1,4167 @@
+#![recursion_limit = "256"]
+/// compiles the same source as `bf!` into a native function running on a `[u8; 30000]` tape
+///
+/// unlike the default type level interpreter cells wrap around and moving left of cell 0 panics,
+/// `,` reads a single byte from `input` and stores 0 on eof
+///
+/// ```
+/// use brainfuck::bf_fn;
+/// bf_fn!(pub fn cat; ,[.,]);
+/// let mut output = Vec::new();
+/// cat(&mut &b"meow"[..], &mut output).unwrap();
+/// assert_eq!(output, b"meow");
+/// ```
+pub use brainfuck_macros::bf_fn;
+// the proc macros behind the wrappers below, they need the path to this crate passed first
+#[doc(hidden)]
+pub use brainfuck_macros as __macros;
+
+/// builds the instruction type of a program running on the given state, a type in parentheses
+/// is spliced in as an `instructions::Snippet`
+///
+/// ```
+/// use brainfuck::{
+///     bf,
+///     brainfuck::{GetOutput, Interpreter},
+///     indexed::Debug,
+///     instructions::Execute,
+///     numbers::One,
+///     snippets::MoveTo,
+/// };
+/// type Code = bf!(Interpreter; ++[->+<]>.);
+/// assert_eq!(GetOutput::<Execute<Code>>::data(), [2]);
+/// type Moved = bf!(Interpreter; ++(MoveTo<One>)>.);
+/// assert_eq!(GetOutput::<Execute<Moved>>::data(), [2]);
+/// ```
+///
+/// anything that is not a command is a compile error pointing at the token
+///
+/// ```compile_fail
+/// use brainfuck::{bf, brainfuck::Interpreter};
+/// type Code = bf!(Interpreter; ++a>.);
+/// ```
+#[macro_export]
+macro_rules! bf {
+    ($($input:tt)*) => {
+        $crate::__macros::bf!($crate; $($input)*)
+    };
+}
+/// like `bf!` but optimizes the program first: `+-` and `><` cancel out, loops that can never
+/// run are dropped and runs of `<>+-` that end where they started become `IncrAt`/`DecrAt`
+///
+/// loops before anything changed the state are only dropped when the state is written out as
+/// an `Interpreter` or an `InterpreterBase` on an empty tape, any other state may start on a
+/// non-zero cell
+///
+/// this assumes the tape is not `indexed::Strict`, a `>` off the end of a strict tape followed
+/// by `<` compiles as if it never happened
+#[macro_export]
+macro_rules! bf_opt {
+    ($($input:tt)*) => {
+        $crate::__macros::bf_opt!($crate; $($input)*)
+    };
+}
+/// builds the instruction type of a program written with named cells instead of brainfuck
+///
+/// - `let a;` or `let a = 3;` declares a cell, cells are given out in the order they are declared
+///   and can be used from their `let` on
+/// - `add a, 3;` and `sub a, 3;` add or subtract a number, `add a, b;` and `sub a, b;` add or
+///   subtract the value of another cell
+/// - `copy a, b;` sets `a` to the value of `b`
+/// - `clear a;` sets `a` to zero
+/// - `print a;` prints `a`
+/// - `while a { .. }` runs its body until `a` is zero
+///
+/// ```
+/// use brainfuck::{
+///     bf_lang,
+///     brainfuck::{GetOutput, Interpreter},
+///     indexed::Debug,
+///     instructions::Execute,
+/// };
+/// type Code = bf_lang!(Interpreter; let a = 3; let b; while a { add b, 2; sub a, 1; } print b;);
+/// assert_eq!(GetOutput::<Execute<Code>>::data(), [6]);
+/// ```
+///
+/// a cell can only be used after its `let`, otherwise the macro points at the use
+///
+/// ```compile_fail
+/// use brainfuck::{bf_lang, brainfuck::Interpreter};
+/// type Code = bf_lang!(Interpreter; let a = 3; add b, a; let b;);
+/// ```
+#[macro_export]
+macro_rules! bf_lang {
+    ($($input:tt)*) => {
+        $crate::__macros::bf_lang!($crate; $($input)*)
+    };
+}
+/// like `bf!` but the program is evaluated in chunks, a chunk ends after every top level loop
+/// or once it is 64 commands long and runs on the state produced by the previous chunk, so the
+/// recursion depth needed by `Execute` is bounded by the largest chunk instead of the whole
+/// program
+///
+/// expands to the final state instead of the code, with the chunks nested in a single type
+///
+/// ```
+/// use brainfuck::{
+///     bf, bf_chunked,
+///     brainfuck::{GetOutput, Interpreter},
+///     indexed::Debug,
+///     instructions::Execute,
+///     TypeNamed,
+/// };
+/// type Final = bf_chunked!(Interpreter; ++[->+<]>.);
+/// // expands to
+/// type Expanded = Execute<bf!(Execute<bf!(Interpreter; ++[->+<])>; >.)>;
+/// assert_eq!(Final::name(), Expanded::name());
+/// assert_eq!(GetOutput::<Final>::data(), [2]);
+/// ```
+///
+/// thousands of commands nest too deep for rustc, `type Name = State` in place of the state
+/// gives every chunk its own type alias named after the final state instead
+///
+/// ```
+/// use brainfuck::{
+///     bf, bf_chunked,
+///     brainfuck::{GetOutput, Interpreter},
+///     indexed::Debug,
+///     instructions::Execute,
+///     TypeNamed,
+/// };
+/// bf_chunked!(type Final = Interpreter; ++[->+<]>.);
+/// // expands to
+/// type ExpandedChunk0 = Execute<bf!(Interpreter; ++[->+<])>;
+/// type Expanded = Execute<bf!(ExpandedChunk0; >.)>;
+/// assert_eq!(FinalChunk0::name(), ExpandedChunk0::name());
+/// assert_eq!(Final::name(), Expanded::name());
+/// assert_eq!(GetOutput::<Final>::data(), [2]);
+/// ```
+#[macro_export]
+macro_rules! bf_chunked {
+    ($($input:tt)*) => {
+        $crate::__macros::bf_chunked!($crate; $($input)*)
+    };
+}
+
+#[cfg(test)]
+macro_rules! actions {
+    ($base:ty) => {
+        $base
+    };
+    ($base:ty>$($rest:tt)*) => {
+        actions!{ @actions $base, $($rest)*}
+    };
+    (@actions $prev:ty, $operation:ident) => {
+        $operation<$prev>
+    };
+    (@actions $prev:ty, $operation:ident>$($rest:tt)*) => {
+        actions!(@actions $operation<$prev>, $($rest)*)
+    }
+}
+macro_rules! ll {
+    () => {
+        crate::linked_list::End
+    };
+    ($val:ident) => {
+        crate::linked_list::Node<$val, ll!()>
+    };
+    ($val:ident, $($rest:ident),+) => {
+        crate::linked_list::Node<$val, ll!{$($rest),+}>
+    };
+}
+macro_rules! list {
+    () => {
+        list!(crate::numbers::Zero; )
+    };
+    ($val:ident) => {
+        list!(crate::numbers::Zero; $val)
+    };
+    ($val:ident, $($rest:ident),+) => {
+        list!(crate::numbers::Zero; $val, $($rest),+)
+    };
+    ($index:ty; $($data:ident),*) => {
+        crate::indexed::Indexed<$index, ll!($($data),*)>
+    };
+}
+
+/// a readable name for a type, used to compare programs and states
+pub trait TypeNamed {
+    fn name() -> String;
+}
+impl TypeNamed for operators::Add {
+    fn name() -> String {
+        "Add".to_owned()
+    }
+}
+impl TypeNamed for operators::Sub {
+    fn name() -> String {
+        "Sub".to_owned()
+    }
+}
+impl TypeNamed for numbers::Zero {
+    fn name() -> String {
+        "0".to_owned()
+    }
+}
+impl<N: numbers::Number> TypeNamed for numbers::NextNumber<N> {
+    fn name() -> String {
+        format!("{}", 1 + N::eval())
+    }
+}
+impl<N: numbers::Number> TypeNamed for numbers::Pos<N> {
+    fn name() -> String {
+        format!("{}", N::eval())
+    }
+}
+impl<N: numbers::Number, Left> TypeNamed for numbers::Byte<N, Left> {
+    fn name() -> String {
+        format!("{}", N::eval())
+    }
+}
+impl<N: numbers::Number> TypeNamed for numbers::Neg<N> {
+    fn name() -> String {
+        format!("-{}", N::eval())
+    }
+}
+impl<Name: TypeNamed, Offset: numbers::Number, Cells> TypeNamed
+    for numbers::Var<Name, Offset, Cells>
+{
+    fn name() -> String {
+        match Offset::eval() {
+            0 => Name::name(),
+            offset => format!("{}{:+}", Name::name(), offset),
+        }
+    }
+}
+impl<Blank> TypeNamed for linked_list::End<Blank> {
+    fn name() -> String {
+        "]".to_owned()
+    }
+}
+impl<Val: TypeNamed, Next: TypeNamed> TypeNamed for linked_list::Node<Val, Next> {
+    fn name() -> String {
+        format!("{}, {}", Val::name(), Next::name())
+    }
+}
+impl<Idx: TypeNamed, List: TypeNamed, Tape> TypeNamed for indexed::Indexed<Idx, List, Tape> {
+    fn name() -> String {
+        format!("List<{},[{}>", Idx::name(), List::name())
+    }
+}
+impl<T: TypeNamed> TypeNamed for instructions::Decr<T> {
+    fn name() -> String {
+        format!("Decr<{}>", T::name())
+    }
+}
+impl<T: TypeNamed> TypeNamed for instructions::Incr<T> {
+    fn name() -> String {
+        format!("Incr<{}>", T::name())
+    }
+}
+impl<T: TypeNamed> TypeNamed for instructions::MoveRight<T> {
+    fn name() -> String {
+        format!("MoveR<{}>", T::name())
+    }
+}
+impl<T: TypeNamed> TypeNamed for instructions::MoveLeft<T> {
+    fn name() -> String {
+        format!("MoveL<{}>", T::name())
+    }
+}
+impl<T: TypeNamed> TypeNamed for instructions::LoopEnd<T> {
+    fn name() -> String {
+        format!("LoopEnd<{}>", T::name())
+    }
+}
+impl<Offset: numbers::Number, T: TypeNamed> TypeNamed for instructions::IncrAt<Offset, T> {
+    fn name() -> String {
+        format!("IncrAt<{}, {}>", Offset::eval(), T::name())
+    }
+}
+impl<Offset: numbers::Number, T: TypeNamed> TypeNamed for instructions::DecrAt<Offset, T> {
+    fn name() -> String {
+        format!("DecrAt<{}, {}>", Offset::eval(), T::name())
+    }
+}
+impl<Stride: numbers::Number, T: TypeNamed> TypeNamed for instructions::ScanRight<Stride, T> {
+    fn name() -> String {
+        format!("ScanR<{}, {}>", Stride::eval(), T::name())
+    }
+}
+impl<Stride: numbers::Number, T: TypeNamed> TypeNamed for instructions::ScanLeft<Stride, T> {
+    fn name() -> String {
+        format!("ScanL<{}, {}>", Stride::eval(), T::name())
+    }
+}
+// a snippet is named after what it expands to
+impl<S: instructions::Snippet<T>, T> TypeNamed for instructions::Splice<S, T>
+where
+    <S as instructions::Snippet<T>>::Chain: TypeNamed,
+{
+    fn name() -> String {
+        <S as instructions::Snippet<T>>::Chain::name()
+    }
+}
+impl<T: TypeNamed> TypeNamed for instructions::LoopStart<T> {
+    fn name() -> String {
+        format!("LoopStart<{}>", T::name())
+    }
+}
+impl<State: TypeNamed> TypeNamed for instructions::Diverges<State> {
+    fn name() -> String {
+        format!("Diverges<{}>", State::name())
+    }
+}
+impl<A: TypeNamed, B: TypeNamed, C, I> TypeNamed for brainfuck::InterpreterBase<A, B, C, I> {
+    fn name() -> String {
+        format!("Interpreter<{}, {}>", A::name(), B::name())
+    }
+}
+
+pub mod operators {
+    pub struct Add;
+    pub struct Sub;
+    /// replaces the value with `V`
+    pub struct Set<V>(V);
+}
+
+pub mod numbers {
+    use crate::{config, operators};
+
+    pub struct Zero;
+    pub struct NextNumber<N>(N);
+
+    macro_rules! define_numbers {
+        ($($number:ident),+) => {
+            define_numbers!{@inner Zero, $($number),+}
+        };
+        (@inner $prev:ident, $next:ident, $($remainder:ident),+) => {
+            pub type $next = NextNumber<$prev>;
+            define_numbers!{@inner $next, $($remainder),+}
+        };
+        (@inner $prev:ident, $next: ident) => {
+            pub type $next = NextNumber<$prev>;
+        };
+    }
+    // handy aliases
+    define_numbers!(One, Two, Three, Four, Five, Six, Seven, Eight, Nine);
+
+    pub trait Operation<Op> {
+        type Output;
+    }
+    impl Operation<operators::Add> for Zero {
+        type Output = NextNumber<Zero>;
+    }
+    impl<N> Operation<operators::Add> for NextNumber<N> {
+        type Output = NextNumber<NextNumber<N>>;
+    }
+    impl<N> Operation<operators::Sub> for NextNumber<N> {
+        type Output = N;
+    }
+    impl Operation<operators::Sub> for Zero {
+        type Output = Zero;
+    }
+    impl<N, V> Operation<operators::Set<V>> for N {
+        type Output = V;
+    }
+
+    /// `Self - N`, saturating at zero
+    pub trait Minus<N> {
+        type Output;
+    }
+    impl<N> Minus<N> for Zero {
+        type Output = Zero;
+    }
+    impl<M> Minus<Zero> for NextNumber<M> {
+        type Output = NextNumber<M>;
+    }
+    impl<M, N> Minus<NextNumber<N>> for NextNumber<M>
+    where
+        M: Minus<N>,
+    {
+        type Output = <M as Minus<N>>::Output;
+    }
+
+    /// `Self + N`
+    pub trait Plus<N> {
+        type Output;
+    }
+    impl<N> Plus<N> for Zero {
+        type Output = N;
+    }
+    impl<M, N> Plus<N> for NextNumber<M>
+    where
+        M: Plus<NextNumber<N>>,
+    {
+        type Output = <M as Plus<NextNumber<N>>>::Output;
+    }
+
+    /// the larger of `Self` and `N`
+    pub trait Max<N> {
+        type Output;
+    }
+    impl<N> Max<N> for Zero {
+        type Output = N;
+    }
+    impl<M> Max<Zero> for NextNumber<M> {
+        type Output = NextNumber<M>;
+    }
+    impl<M, N> Max<NextNumber<N>> for NextNumber<M>
+    where
+        M: Max<N>,
+    {
+        type Output = NextNumber<<M as Max<N>>::Output>;
+    }
+    /// how far a cell is from zero
+    pub trait Magnitude {
+        type Output;
+    }
+    impl Magnitude for Zero {
+        type Output = Zero;
+    }
+    impl<N> Magnitude for NextNumber<N> {
+        type Output = NextNumber<N>;
+    }
+    impl<N> Magnitude for Pos<N> {
+        type Output = N;
+    }
+    impl<N> Magnitude for Neg<N> {
+        type Output = N;
+    }
+    impl<N, Left> Magnitude for Byte<N, Left> {
+        type Output = N;
+    }
+
+    /// signed numbers for cells that go below zero, `Pos<Zero>` is zero and `Neg<N>` is `-N` for
+    /// a non-zero `N`
+    pub struct Pos<N>(N);
+    pub struct Neg<N>(N);
+    impl<N> Operation<operators::Add> for Pos<N> {
+        type Output = Pos<NextNumber<N>>;
+    }
+    impl Operation<operators::Add> for Neg<NextNumber<Zero>> {
+        type Output = Pos<Zero>;
+    }
+    impl<N> Operation<operators::Add> for Neg<NextNumber<NextNumber<N>>> {
+        type Output = Neg<NextNumber<N>>;
+    }
+    impl<N> Operation<operators::Sub> for Pos<NextNumber<N>> {
+        type Output = Pos<N>;
+    }
+    impl Operation<operators::Sub> for Pos<Zero> {
+        type Output = Neg<NextNumber<Zero>>;
+    }
+    impl<N> Operation<operators::Sub> for Neg<N> {
+        type Output = Neg<NextNumber<N>>;
+    }
+
+    pub struct True;
+    pub struct False;
+    /// `Then` for `True` and `Else` for `False`
+    pub trait Select<Then, Else> {
+        type Output;
+    }
+    impl<Then, Else> Select<Then, Else> for True {
+        type Output = Then;
+    }
+    impl<Then, Else> Select<Then, Else> for False {
+        type Output = Else;
+    }
+    /// `True` if `Self == N`
+    pub trait Equal<N> {
+        type Output;
+    }
+    impl Equal<Zero> for Zero {
+        type Output = True;
+    }
+    impl<N> Equal<NextNumber<N>> for Zero {
+        type Output = False;
+    }
+    impl<M> Equal<Zero> for NextNumber<M> {
+        type Output = False;
+    }
+    impl<M, N> Equal<NextNumber<N>> for NextNumber<M>
+    where
+        M: Equal<N>,
+    {
+        type Output = <M as Equal<N>>::Output;
+    }
+
+    /// `True` if `Self == N` can be seen within `Fuel` levels of recursion and `False` if not,
+    /// unlike `Equal` this works for whole states without needing a recursion limit as large as
+    /// the biggest value in them
+    pub trait Matches<N, Fuel> {
+        type Output;
+    }
+    /// the number of `x`s
+    macro_rules! peano {
+        () => { Zero };
+        (x $($rest:tt)*) => { NextNumber<peano!($($rest)*)> };
+    }
+    // spelled out, a projection like `Plus` here is not normalized in the where clauses using it.
+    // more fuel catches loops on longer tapes but every loop iteration pays for it
+    pub type MatchFuel = peano!(x x x x x x x x x x x x x x x x);
+    impl<Fuel> Matches<Zero, Fuel> for Zero {
+        type Output = True;
+    }
+    impl<N, Fuel> Matches<NextNumber<N>, Fuel> for Zero {
+        type Output = False;
+    }
+    impl<M, Fuel> Matches<Zero, Fuel> for NextNumber<M> {
+        type Output = False;
+    }
+    impl<M, N> Matches<NextNumber<N>, Zero> for NextNumber<M> {
+        type Output = False;
+    }
+    impl<M, N, Fuel> Matches<NextNumber<N>, NextNumber<Fuel>> for NextNumber<M>
+    where
+        M: Matches<N, Fuel>,
+    {
+        type Output = <M as Matches<N, Fuel>>::Output;
+    }
+    impl<M, N, Fuel> Matches<Pos<N>, Fuel> for Pos<M>
+    where
+        M: Matches<N, Fuel>,
+    {
+        type Output = <M as Matches<N, Fuel>>::Output;
+    }
+    impl<M, N, Fuel> Matches<Neg<N>, Fuel> for Neg<M>
+    where
+        M: Matches<N, Fuel>,
+    {
+        type Output = <M as Matches<N, Fuel>>::Output;
+    }
+    impl<M, N, Fuel> Matches<Neg<N>, Fuel> for Pos<M> {
+        type Output = False;
+    }
+    impl<M, N, Fuel> Matches<Pos<N>, Fuel> for Neg<M> {
+        type Output = False;
+    }
+    impl<M, N, L, R, Fuel> Matches<Byte<N, R>, Fuel> for Byte<M, L>
+    where
+        M: Matches<N, Fuel>,
+    {
+        type Output = <M as Matches<N, Fuel>>::Output;
+    }
+    impl<A1, B1, A2, B2, Fuel> Matches<(A2, B2), Fuel> for (A1, B1)
+    where
+        A1: Matches<A2, Fuel>,
+        <A1 as Matches<A2, Fuel>>::Output: AndMatches<B1, B2, Fuel>,
+    {
+        type Output = <<A1 as Matches<A2, Fuel>>::Output as AndMatches<B1, B2, Fuel>>::Output;
+    }
+    /// `A` matches `B` if `Self` is `True`, so comparisons stop at the first difference
+    pub trait AndMatches<A, B, Fuel> {
+        type Output;
+    }
+    impl<A: Matches<B, Fuel>, B, Fuel> AndMatches<A, B, Fuel> for True {
+        type Output = <A as Matches<B, Fuel>>::Output;
+    }
+    impl<A, B, Fuel> AndMatches<A, B, Fuel> for False {
+        type Output = False;
+    }
+    /// `True` for any number that is not zero, this is what loops and scans check
+    pub trait Truthy {
+        type Output;
+    }
+    impl Truthy for Zero {
+        type Output = False;
+    }
+    impl<N> Truthy for NextNumber<N> {
+        type Output = True;
+    }
+    impl<N: Truthy> Truthy for Pos<N> {
+        type Output = <N as Truthy>::Output;
+    }
+    impl<N> Truthy for Neg<N> {
+        type Output = True;
+    }
+
+    /// a byte for `config::Wrap8` cells, `Left` is what is left until 255 so that neither
+    /// direction has to compare against 255 to wrap
+    pub struct Byte<N, Left>(N, Left);
+    type Double<N> = <N as Plus<N>>::Output;
+    pub type ByteMax =
+        <Double<Double<Double<Double<Double<Double<Double<Two>>>>>>> as Operation<
+            operators::Sub,
+        >>::Output;
+    impl<N, Left> Operation<operators::Add> for Byte<N, NextNumber<Left>> {
+        type Output = Byte<NextNumber<N>, Left>;
+    }
+    impl<N> Operation<operators::Add> for Byte<N, Zero> {
+        type Output = Byte<Zero, ByteMax>;
+    }
+    impl<N, Left> Operation<operators::Sub> for Byte<NextNumber<N>, Left> {
+        type Output = Byte<N, NextNumber<Left>>;
+    }
+    impl<Left> Operation<operators::Sub> for Byte<Zero, Left> {
+        type Output = Byte<ByteMax, Zero>;
+    }
+    impl<N: Truthy, Left> Truthy for Byte<N, Left> {
+        type Output = <N as Truthy>::Output;
+    }
+
+    /// a symbolic cell of `Cells` cells, whatever `Name` stood for at the start plus `Offset`.
+    /// the offset is a signed number, saturating cells only let it go back down to zero as
+    /// whether the cell would saturate is not known
+    ///
+    /// ```compile_fail,E0277
+    /// use brainfuck::numbers::{self, Var};
+    /// use brainfuck::operators::Sub;
+    /// struct X;
+    /// // `x - 1` is `x` again only if `x` was zero
+    /// type Decremented = <Var<X> as numbers::Operation<Sub>>::Output;
+    /// let _: Option<Decremented> = None;
+    /// ```
+    pub struct Var<Name, Offset = Pos<Zero>, Cells = config::Saturating>(Name, Offset, Cells);
+    impl<Name, Offset, Cells> Operation<operators::Add> for Var<Name, Offset, Cells>
+    where
+        Offset: Operation<operators::Add>,
+    {
+        type Output = Var<Name, <Offset as Operation<operators::Add>>::Output, Cells>;
+    }
+    impl<Name, Offset, Cells> Operation<operators::Sub> for Var<Name, Offset, Cells>
+    where
+        Cells: Decrement<Offset>,
+    {
+        type Output = Var<Name, <Cells as Decrement<Offset>>::Offset, Cells>;
+    }
+    /// the offset of a symbolic cell of `Self` cells after decrementing it
+    #[diagnostic::on_unimplemented(
+        message = "`{Self}` cells saturate, a symbolic cell at offset `{Offset}` may be zero",
+        label = "decrementing it is only known for signed or wrapping cells"
+    )]
+    pub trait Decrement<Offset> {
+        type Offset;
+    }
+    impl<N> Decrement<Pos<NextNumber<N>>> for config::Saturating {
+        type Offset = Pos<N>;
+    }
+    impl<Offset: Operation<operators::Sub>> Decrement<Offset> for config::Signed {
+        type Offset = <Offset as Operation<operators::Sub>>::Output;
+    }
+    impl<Offset: Operation<operators::Sub>> Decrement<Offset> for config::Wrap8 {
+        type Offset = <Offset as Operation<operators::Sub>>::Output;
+    }
+    #[diagnostic::on_unimplemented(
+        message = "`{Self}` is symbolic, loops and scans need to know whether the cell is zero",
+        label = "checked by a loop or scan"
+    )]
+    pub trait Concrete {}
+    impl<Name, Offset, Cells> Truthy for Var<Name, Offset, Cells>
+    where
+        Self: Concrete,
+    {
+        type Output = False;
+    }
+
+    pub trait Number {
+        /// the value in const context
+        const VALUE: i64;
+        fn eval() -> i64;
+    }
+    impl Number for Zero {
+        const VALUE: i64 = 0;
+        fn eval() -> i64 {
+            0
+        }
+    }
+    impl<N: Number> Number for NextNumber<N> {
+        const VALUE: i64 = 1 + N::VALUE;
+        fn eval() -> i64 {
+            // whilst our numbers can overflow an i64, rustc should reach the recursion limit/crash/oom before this happens
+            1 + N::eval()
+        }
+    }
+    impl<N: Number> Number for Pos<N> {
+        const VALUE: i64 = N::VALUE;
+        fn eval() -> i64 {
+            N::eval()
+        }
+    }
+    impl<N: Number> Number for Neg<N> {
+        const VALUE: i64 = -N::VALUE;
+        fn eval() -> i64 {
+            -N::eval()
+        }
+    }
+    impl<N: Number, Left> Number for Byte<N, Left> {
+        const VALUE: i64 = N::VALUE;
+        fn eval() -> i64 {
+            N::eval()
+        }
+    }
+    #[cfg(test)]
+    mod tests {
+        use crate::{
+            config,
+            numbers::{self, Neg, Number, Pos, Three, Two, Var, Zero},
+            operators, TypeNamed,
+        };
+        type Add<T> = <T as numbers::Operation<operators::Add>>::Output;
+        type Sub<T> = <T as numbers::Operation<operators::Sub>>::Output;
+        #[test]
+        fn adds() {
+            assert_eq!(<actions!(Zero > Add > Add > Add)>::eval(), 3);
+        }
+        #[test]
+        fn add_sub() {
+            assert_eq!(<actions!(Zero > Add > Add > Sub)>::eval(), 1);
+        }
+        #[test]
+        fn plus() {
+            assert_eq!(<<Three as numbers::Plus<Two>>::Output>::eval(), 5);
+            assert_eq!(<<Zero as numbers::Plus<Two>>::Output>::eval(), 2);
+            assert_eq!(<<Three as numbers::Plus<Zero>>::Output>::eval(), 3);
+        }
+        #[test]
+        fn signed() {
+            type SignedZero = Pos<Zero>;
+            assert_eq!(<actions!(SignedZero > Sub > Sub)>::eval(), -2);
+            assert_eq!(<actions!(SignedZero > Sub > Sub > Add > Add > Add)>::eval(), 1);
+            // crossing zero gives back `Pos<Zero>` and never `Neg<Zero>`
+            assert_eq!(<actions!(SignedZero > Sub > Add)>::name(), "0");
+            assert_eq!(<actions!(SignedZero > Add > Sub)>::name(), "0");
+            assert_eq!(<Neg<Two>>::name(), "-2");
+        }
+        #[test]
+        fn symbolic() {
+            struct X;
+            impl TypeNamed for X {
+                fn name() -> String {
+                    "x".to_owned()
+                }
+            }
+            type Start = Var<X>;
+            assert_eq!(<actions!(Start > Add > Add)>::name(), "x+2");
+            // saturating cells only come back down to where they started, `-+` does not compile
+            assert_eq!(<actions!(Start > Add > Add > Sub > Sub)>::name(), "x");
+            type Signed = Var<X, Pos<Zero>, config::Signed>;
+            assert_eq!(<actions!(Signed > Sub > Sub > Sub)>::name(), "x-3");
+            assert_eq!(<actions!(Signed > Sub > Add)>::name(), "x");
+            assert_eq!(<actions!(Signed > Add > Sub)>::name(), "x");
+            type Wrapped = Var<X, Pos<Zero>, config::Wrap8>;
+            assert_eq!(<actions!(Wrapped > Sub > Add)>::name(), "x");
+            assert_eq!(<actions!(Wrapped > Add > Sub)>::name(), "x");
+        }
+    }
+}
+
+pub mod linked_list {
+    use crate::numbers::{self, NextNumber, Number, Zero};
+
+    /// the end of a list, reading past it gives `Blank`
+    pub struct End<Blank = Zero>(Blank);
+    pub struct Node<Val, Next>(Val, Next);
+
+    // lists only match when they have the same length, a trailing blank cell counts, every
+    // node uses up one level of `Fuel`
+    impl<A, B, Fuel> numbers::Matches<End<B>, Fuel> for End<A> {
+        type Output = numbers::True;
+    }
+    impl<A, Val, Next, Fuel> numbers::Matches<Node<Val, Next>, Fuel> for End<A> {
+        type Output = numbers::False;
+    }
+    impl<B, Val, Next, Fuel> numbers::Matches<End<B>, Fuel> for Node<Val, Next> {
+        type Output = numbers::False;
+    }
+    impl<V1, N1, V2, N2> numbers::Matches<Node<V2, N2>, Zero> for Node<V1, N1> {
+        type Output = numbers::False;
+    }
+    impl<V1, N1, V2, N2, Fuel> numbers::Matches<Node<V2, N2>, NextNumber<Fuel>> for Node<V1, N1>
+    where
+        (V1, N1): numbers::Matches<(V2, N2), Fuel>,
+    {
+        type Output = <(V1, N1) as numbers::Matches<(V2, N2), Fuel>>::Output;
+    }
+
+    pub trait Index<Idx> {
+        type Val;
+        type List;
+    }
+    impl<Val, Next> Index<Zero> for Node<Val, Next> {
+        type List = Node<Val, Next>;
+        type Val = Val;
+    }
+    impl<Val, Next, Idx> Index<NextNumber<Idx>> for Node<Val, Next>
+    where
+        Next: Index<Idx>,
+    {
+        type List = Node<Val, Next>;
+        type Val = <Next as Index<Idx>>::Val;
+    }
+    // when indexing out of bounds grow the ll
+    impl<Blank> Index<Zero> for End<Blank> {
+        type List = Node<Self::Val, End<Blank>>;
+        type Val = Blank;
+    }
+    impl<Idx, Blank> Index<NextNumber<Idx>> for End<Blank>
+    where
+        End<Blank>: Index<Idx>,
+    {
+        type List = Node<Blank, End<Blank>>;
+        type Val = <End<Blank> as Index<Idx>>::Val;
+    }
+
+    /// implemented by the lists that give `Blank` when read past their end
+    pub trait Padded<Blank> {}
+    impl<Blank> Padded<Blank> for End<Blank> {}
+    impl<Val, Next: Padded<Blank>, Blank> Padded<Blank> for Node<Val, Next> {}
+
+    pub trait Len {
+        type LenOut;
+    }
+    impl<Blank> Len for End<Blank> {
+        type LenOut = Zero;
+    }
+    impl<V, Next> Len for Node<V, Next>
+    where
+        Next: Len,
+    {
+        type LenOut = NextNumber<<Next as Len>::LenOut>;
+    }
+    pub trait LLOp<Op, Idx> {
+        type List;
+    }
+    impl<Val, Next, Op> LLOp<Op, Zero> for Node<Val, Next>
+    where
+        Val: numbers::Operation<Op>,
+    {
+        type List = Node<<Val as numbers::Operation<Op>>::Output, Next>;
+    }
+    impl<Op, Blank> LLOp<Op, Zero> for End<Blank>
+    where
+        Blank: numbers::Operation<Op>,
+    {
+        type List = Node<<Blank as numbers::Operation<Op>>::Output, End<Blank>>;
+    }
+    impl<Val, Next, Idx, Op> LLOp<Op, NextNumber<Idx>> for Node<Val, Next>
+    where
+        Next: LLOp<Op, Idx>,
+    {
+        type List = Node<Val, <Next as LLOp<Op, Idx>>::List>;
+    }
+    impl<Idx, Op, Blank> LLOp<Op, NextNumber<Idx>> for End<Blank>
+    where
+        End<Blank>: LLOp<Op, Idx>,
+    {
+        type List = Node<Blank, <End<Blank> as LLOp<Op, Idx>>::List>;
+    }
+
+    pub trait Push<Val> {
+        type List;
+    }
+    impl<ToAdd, Blank> Push<ToAdd> for End<Blank> {
+        type List = Node<ToAdd, End<Blank>>;
+    }
+    impl<ToAdd, Next, Val> Push<ToAdd> for Node<Val, Next>
+    where
+        Next: Push<ToAdd>,
+    {
+        type List = Node<Val, <Next as Push<ToAdd>>::List>;
+    }
+
+    /// `Self` reversed
+    pub trait Reverse {
+        type List;
+    }
+    impl<LL: ReverseOnto<End>> Reverse for LL {
+        type List = <LL as ReverseOnto<End>>::List;
+    }
+    /// `Self` reversed in front of `Acc`
+    pub trait ReverseOnto<Acc> {
+        type List;
+    }
+    impl<Acc, Blank> ReverseOnto<Acc> for End<Blank> {
+        type List = Acc;
+    }
+    impl<Acc, Val, Next> ReverseOnto<Acc> for Node<Val, Next>
+    where
+        Next: ReverseOnto<Node<Val, Acc>>,
+    {
+        type List = <Next as ReverseOnto<Node<Val, Acc>>>::List;
+    }
+    #[derive(Debug)]
+    pub struct InvalidCharError;
+    pub trait ToVec {
+        fn to_vec() -> Vec<i64>;
+        fn to_string() -> Result<String, InvalidCharError> {
+            let data = Self::to_vec()
+                .into_iter()
+                .map(|x| u8::try_from(x).map_err(|_| InvalidCharError))
+                .collect::<Result<Vec<_>, _>>()?;
+            String::from_utf8(data).map_err(|_| InvalidCharError)
+        }
+    }
+    impl<Blank> ToVec for End<Blank> {
+        fn to_vec() -> Vec<i64> {
+            vec![]
+        }
+    }
+    impl<N: Number, Next: ToVec> ToVec for Node<N, Next> {
+        fn to_vec() -> Vec<i64> {
+            let mut a = vec![N::eval()];
+            a.extend(Next::to_vec());
+            a
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use std::any::type_name;
+
+        use super::ToVec;
+        use crate::numbers::{Number, One, Three, Two, Zero};
+        type Index<LL, Idx> = <LL as crate::linked_list::Index<Idx>>::Val;
+        type Len<LL> = <LL as crate::linked_list::Len>::LenOut;
+        type Push<LL, Item> = <LL as crate::linked_list::Push<Item>>::List;
+        type Reverse<LL> = <LL as crate::linked_list::Reverse>::List;
+        #[test]
+        fn index() {
+            type Data = ll!(Zero, One, Two, Three);
+            assert_eq!(<Index<Data, Zero>>::eval(), 0);
+            assert_eq!(<Index<Data, One>>::eval(), 1);
+            assert_eq!(<Index<Data, Two>>::eval(), 2);
+            assert_eq!(<Len<Data>>::eval(), 4);
+        }
+        #[test]
+        fn index_out_of_bounds() {
+            type Data = ll!();
+            println!("{}", type_name::<Data>());
+            assert_eq!(<Index<Data, Two>>::eval(), 0);
+            // this does not yet grow the array but Index returns the new array that has grown
+            assert_eq!(<Len<Data>>::eval(), 0);
+        }
+        #[test]
+        fn test_push() {
+            type Data = ll!();
+            type Data2 = Push<Push<Data, Two>, One>;
+            assert_eq!(Data2::to_vec(), [2, 1]);
+        }
+        #[test]
+        fn reverse() {
+            type Data = ll!(One, Two, Three);
+            assert_eq!(<Reverse<Data>>::to_vec(), [3, 2, 1]);
+            assert_eq!(<Reverse<ll!()>>::to_vec(), []);
+        }
+    }
+}
+
+pub mod indexed {
+    use crate::linked_list::InvalidCharError;
+    use crate::numbers::Number;
+    use crate::{brainfuck, instructions, linked_list, numbers, operators};
+    use crate::{
+        linked_list::End,
+        numbers::{False, NextNumber, Select, True, Truthy, Zero},
+    };
+
+    /// a tape, `Tape` decides what happens when the index moves past either end
+    pub struct Indexed<Idx, FirstNode, Tape = Unbounded>(Idx, FirstNode, Tape);
+    pub type EmptyIndexed = Indexed<Zero, End>;
+
+    /// grows to the right forever, moving left of cell 0 stays at cell 0
+    pub struct Unbounded;
+    /// `Len` cells where moving past either end wraps around to the other one
+    pub struct Circular<Len>(Len);
+    /// `Len` cells where moving past either end does not compile
+    ///
+    /// ```compile_fail,E0277
+    /// use brainfuck::{
+    ///     bf,
+    ///     brainfuck::{GetOutput, Interpreter},
+    ///     config::{Config, Saturating},
+    ///     indexed::{Debug, Strict},
+    ///     instructions::Execute,
+    ///     numbers::Three,
+    /// };
+    /// type Code = bf!(Interpreter<Config<Saturating, Strict<Three>>>; +>>>.);
+    /// let _ = GetOutput::<Execute<Code>>::data();
+    /// ```
+    pub struct Strict<Len>(Len);
+
+    /// where the index ends up when moving from `Idx` on tape `Self`
+    pub trait Move<Op, Idx> {
+        type Idx;
+    }
+    impl<Op, Idx> Move<Op, Idx> for Unbounded
+    where
+        Idx: numbers::Operation<Op>,
+    {
+        type Idx = <Idx as numbers::Operation<Op>>::Output;
+    }
+    impl<Len, Idx> Move<operators::Add, Idx> for Circular<Len>
+    where
+        NextNumber<Idx>: numbers::Equal<Len>,
+        <NextNumber<Idx> as numbers::Equal<Len>>::Output: numbers::Select<Zero, NextNumber<Idx>>,
+    {
+        type Idx = <<NextNumber<Idx> as numbers::Equal<Len>>::Output as numbers::Select<
+            Zero,
+            NextNumber<Idx>,
+        >>::Output;
+    }
+    impl<Len> Move<operators::Sub, Zero> for Circular<Len>
+    where
+        Len: numbers::Operation<operators::Sub>,
+    {
+        type Idx = <Len as numbers::Operation<operators::Sub>>::Output;
+    }
+    impl<Len, Idx> Move<operators::Sub, NextNumber<Idx>> for Circular<Len> {
+        type Idx = Idx;
+    }
+    impl<Len, Idx> Move<operators::Add, Idx> for Strict<Len>
+    where
+        NextNumber<Idx>: Below<Len>,
+    {
+        type Idx = NextNumber<Idx>;
+    }
+    impl<Len, Idx> Move<operators::Sub, Idx> for Strict<Len>
+    where
+        Idx: NotFirst + numbers::Operation<operators::Sub>,
+    {
+        type Idx = <Idx as numbers::Operation<operators::Sub>>::Output;
+    }
+    #[diagnostic::on_unimplemented(message = "the pointer moves past the end of a strict tape")]
+    pub trait Below<Len> {}
+    impl<N> Below<NextNumber<N>> for Zero {}
+    impl<M: Below<N>, N> Below<NextNumber<N>> for NextNumber<M> {}
+    #[diagnostic::on_unimplemented(message = "the pointer moves left of the first cell of a strict tape")]
+    pub trait NotFirst {}
+    impl<N> NotFirst for NextNumber<N> {}
+
+    /// `Move` `Times` times
+    #[doc(hidden)]
+    pub trait Moves<Op, Idx, Times> {
+        type Idx;
+    }
+    impl<Op, Idx, Tape> Moves<Op, Idx, Zero> for Tape {
+        type Idx = Idx;
+    }
+    impl<Op, Idx, Tape, Times> Moves<Op, Idx, NextNumber<Times>> for Tape
+    where
+        Tape: Move<Op, Idx>,
+        Tape: Moves<Op, <Tape as Move<Op, Idx>>::Idx, Times>,
+    {
+        type Idx = <Tape as Moves<Op, <Tape as Move<Op, Idx>>::Idx, Times>>::Idx;
+    }
+
+    impl<I1, L1, I2, L2, Tape, Fuel> numbers::Matches<Indexed<I2, L2, Tape>, Fuel>
+        for Indexed<I1, L1, Tape>
+    where
+        (I1, L1): numbers::Matches<(I2, L2), Fuel>,
+    {
+        type Output = <(I1, L1) as numbers::Matches<(I2, L2), Fuel>>::Output;
+    }
+
+    pub trait Get {
+        type Val;
+    }
+    impl<Idx, FirstNode, Tape> Get for Indexed<Idx, FirstNode, Tape>
+    where
+        FirstNode: linked_list::Index<Idx>,
+    {
+        type Val = <FirstNode as linked_list::Index<Idx>>::Val;
+    }
+    impl<Ram: Get, Output, C, Input> Get for brainfuck::InterpreterBase<Ram, Output, C, Input> {
+        type Val = <Ram as Get>::Val;
+    }
+    // loops and scans stop at once so the divergence reaches the end of the program
+    impl<State> Get for instructions::Diverges<State> {
+        type Val = Zero;
+    }
+
+    /// the cell `Offset` cells to the right of the index
+    pub trait GetAt<Offset> {
+        type Val;
+    }
+    impl<Offset, Idx, FirstNode, Tape> GetAt<Offset> for Indexed<Idx, FirstNode, Tape>
+    where
+        Tape: Moves<operators::Add, Idx, Offset>,
+        FirstNode: linked_list::Index<<Tape as Moves<operators::Add, Idx, Offset>>::Idx>,
+    {
+        type Val = <FirstNode as linked_list::Index<
+            <Tape as Moves<operators::Add, Idx, Offset>>::Idx,
+        >>::Val;
+    }
+    impl<Offset, Ram, Output, C, Input> GetAt<Offset>
+        for brainfuck::InterpreterBase<Ram, Output, C, Input>
+    where
+        Ram: GetAt<Offset>,
+    {
+        type Val = <Ram as GetAt<Offset>>::Val;
+    }
+    /// the index itself
+    pub trait Position {
+        type Idx;
+    }
+    impl<Idx, FirstNode, Tape> Position for Indexed<Idx, FirstNode, Tape> {
+        type Idx = Idx;
+    }
+    impl<Ram: Position, Output, C, Input> Position
+        for brainfuck::InterpreterBase<Ram, Output, C, Input>
+    {
+        type Idx = <Ram as Position>::Idx;
+    }
+    impl<State> Position for instructions::Diverges<State> {
+        type Idx = Zero;
+    }
+
+    pub trait Len {
+        type LenOut;
+    }
+    impl<Idx, FirstNode, Tape> Len for Indexed<Idx, FirstNode, Tape>
+    where
+        FirstNode: linked_list::Len,
+    {
+        type LenOut = <FirstNode as linked_list::Len>::LenOut;
+    }
+
+    pub trait VecOp<Op> {
+        type Indexed;
+    }
+    impl<Op, Idx, FirstNode, Tape> VecOp<Op> for Indexed<Idx, FirstNode, Tape>
+    where
+        FirstNode: linked_list::LLOp<Op, Idx>,
+    {
+        type Indexed = Indexed<Idx, <FirstNode as linked_list::LLOp<Op, Idx>>::List, Tape>;
+    }
+
+    /// like `VecOp` but on the cell `Offset` cells to the right of the index
+    pub trait VecOpAt<Op, Offset> {
+        type Indexed;
+    }
+    impl<Op, Offset, Idx, FirstNode, Tape> VecOpAt<Op, Offset> for Indexed<Idx, FirstNode, Tape>
+    where
+        Tape: Moves<operators::Add, Idx, Offset>,
+        FirstNode: linked_list::LLOp<Op, <Tape as Moves<operators::Add, Idx, Offset>>::Idx>,
+    {
+        type Indexed = Indexed<
+            Idx,
+            <FirstNode as linked_list::LLOp<
+                Op,
+                <Tape as Moves<operators::Add, Idx, Offset>>::Idx,
+            >>::List,
+            Tape,
+        >;
+    }
+
+    pub trait IndexOp<Op> {
+        type Indexed;
+    }
+    impl<Op, Idx, FirstNode, Tape> IndexOp<Op> for Indexed<Idx, FirstNode, Tape>
+    where
+        Tape: Move<Op, Idx>,
+    {
+        type Indexed = Indexed<<Tape as Move<Op, Idx>>::Idx, FirstNode, Tape>;
+    }
+
+    /// apply `IndexOp<Op>` `Times` times
+    #[doc(hidden)]
+    pub trait IndexOps<Op, Times> {
+        type Indexed;
+    }
+    impl<Op, T> IndexOps<Op, Zero> for T {
+        type Indexed = T;
+    }
+    impl<Op, T, Times> IndexOps<Op, NextNumber<Times>> for T
+    where
+        T: IndexOp<Op>,
+        <T as IndexOp<Op>>::Indexed: IndexOps<Op, Times>,
+    {
+        type Indexed = <<T as IndexOp<Op>>::Indexed as IndexOps<Op, Times>>::Indexed;
+    }
+
+    /// move the index `Stride` cells at a time until it points to a zero cell, a scan that
+    /// comes back to where it started or is stuck at cell 0 runs forever and ends in
+    /// `instructions::Diverges`
+    #[doc(hidden)]
+    pub trait Scan<Op, Stride> {
+        type Indexed;
+    }
+    type Nonzero<T> = <<T as Get>::Val as Truthy>::Output;
+    type Index<T> = <T as Position>::Idx;
+    impl<Op, Stride, T> Scan<Op, Stride> for T
+    where
+        T: Get + Position,
+        <T as Get>::Val: Truthy,
+        T: ScanFrom<Op, Stride, Index<T>, Nonzero<T>>,
+    {
+        type Indexed = <T as ScanFrom<Op, Stride, Index<T>, Nonzero<T>>>::Indexed;
+    }
+    /// `Scan` from `Self` after starting at `Start`, `Cond` is whether the current cell is not 0
+    #[doc(hidden)]
+    pub trait ScanFrom<Op, Stride, Start, Cond> {
+        type Indexed;
+    }
+    impl<Op, Stride, Start, T> ScanFrom<Op, Stride, Start, False> for T {
+        type Indexed = T;
+    }
+    type Moved<T, Op, Stride> = <T as IndexOps<Op, Stride>>::Indexed;
+    type Same<A, B> = <A as numbers::Equal<B>>::Output;
+    // scans only move the index, so the state repeats exactly when the index does
+    type Repeats<T, Op, Stride, Start> = <Same<Index<Moved<T, Op, Stride>>, Start> as Select<
+        True,
+        Same<Index<Moved<T, Op, Stride>>, Index<T>>,
+    >>::Output;
+    impl<Op, Stride, Start, T> ScanFrom<Op, Stride, Start, True> for T
+    where
+        T: IndexOps<Op, Stride> + Position,
+        Moved<T, Op, Stride>: Position,
+        Index<Moved<T, Op, Stride>>: numbers::Equal<Start> + numbers::Equal<Index<T>>,
+        Same<Index<Moved<T, Op, Stride>>, Start>:
+            Select<True, Same<Index<Moved<T, Op, Stride>>, Index<T>>>,
+        Moved<T, Op, Stride>: ScanStep<Op, Stride, Start, Repeats<T, Op, Stride, Start>>,
+    {
+        type Indexed = <Moved<T, Op, Stride> as ScanStep<
+            Op,
+            Stride,
+            Start,
+            Repeats<T, Op, Stride, Start>,
+        >>::Indexed;
+    }
+    /// carry on with a scan unless `Repeated` says the index was seen before
+    #[doc(hidden)]
+    pub trait ScanStep<Op, Stride, Start, Repeated> {
+        type Indexed;
+    }
+    impl<Op, Stride, Start, T> ScanStep<Op, Stride, Start, True> for T {
+        type Indexed = instructions::Diverges<T>;
+    }
+    impl<Op, Stride, Start, T> ScanStep<Op, Stride, Start, False> for T
+    where
+        T: Get,
+        <T as Get>::Val: Truthy,
+        T: ScanFrom<Op, Stride, Start, Nonzero<T>>,
+    {
+        type Indexed = <T as ScanFrom<Op, Stride, Start, Nonzero<T>>>::Indexed;
+    }
+
+    /// add `Value` in front of the list
+    pub trait Prepend<Value> {
+        type Indexed;
+    }
+    impl<Value, Idx, FirstNode, Tape> Prepend<Value> for Indexed<Idx, FirstNode, Tape> {
+        type Indexed = Indexed<Idx, linked_list::Node<Value, FirstNode>, Tape>;
+    }
+
+    pub trait Reverse {
+        type Indexed;
+    }
+    impl<Idx, FirstNode, Tape> Reverse for Indexed<Idx, FirstNode, Tape>
+    where
+        FirstNode: linked_list::Reverse,
+    {
+        type Indexed = Indexed<Idx, <FirstNode as linked_list::Reverse>::List, Tape>;
+    }
+
+    pub trait Debug {
+        fn index() -> usize;
+        fn data() -> Vec<i64>;
+        fn string() -> Result<String, InvalidCharError>;
+    }
+    impl<FirstNode, Idx, Tape> Debug for Indexed<Idx, FirstNode, Tape>
+    where
+        FirstNode: linked_list::ToVec,
+        Idx: Number,
+    {
+        fn index() -> usize {
+            Idx::eval() as usize
+        }
+        fn data() -> Vec<i64> {
+            FirstNode::to_vec()
+        }
+        fn string() -> Result<String, InvalidCharError> {
+            FirstNode::to_string()
+        }
+    }
+}
+
+pub mod instructions {
+    use crate::{
+        brainfuck,
+        indexed::{self, Get},
+        numbers::{self, False, NextNumber, True, Zero},
+        operators,
+    };
+
+    /// instructions are evaluated in continuation passing style, `State` is the state the body
+    /// of the innermost enclosing loop starts from. a loop body is only stored once, in its
+    /// `LoopEnd`, and is run again on the state every iteration leaves
+    pub trait Instruction<State> {
+        /// execute the instruction and get the result
+        type Exec;
+    }
+    // the start of the program, outside of any loop `State` is never used
+    impl<Ram, Output, C, Input, State> Instruction<State>
+        for brainfuck::InterpreterBase<Ram, Output, C, Input>
+    {
+        type Exec = Self;
+    }
+    impl<Index, Val, Tape, State> Instruction<State> for indexed::Indexed<Index, Val, Tape> {
+        type Exec = Self;
+    }
+
+    pub struct Decr<T>(T);
+    impl<T: Instruction<State>, State> Instruction<State> for Decr<T>
+    where
+        <T as Instruction<State>>::Exec: indexed::VecOp<operators::Sub>,
+    {
+        type Exec = <<T as Instruction<State>>::Exec as indexed::VecOp<operators::Sub>>::Indexed;
+    }
+    pub struct Incr<T>(T);
+    impl<T: Instruction<State>, State> Instruction<State> for Incr<T>
+    where
+        <T as Instruction<State>>::Exec: indexed::VecOp<operators::Add>,
+    {
+        type Exec = <<T as Instruction<State>>::Exec as indexed::VecOp<operators::Add>>::Indexed;
+    }
+    pub struct MoveRight<T>(T);
+    impl<T: Instruction<State>, State> Instruction<State> for MoveRight<T>
+    where
+        <T as Instruction<State>>::Exec: indexed::IndexOp<operators::Add>,
+    {
+        type Exec = <<T as Instruction<State>>::Exec as indexed::IndexOp<operators::Add>>::Indexed;
+    }
+
+    pub struct MoveLeft<T>(T);
+    impl<T: Instruction<State>, State> Instruction<State> for MoveLeft<T>
+    where
+        <T as Instruction<State>>::Exec: indexed::IndexOp<operators::Sub>,
+    {
+        type Exec = <<T as Instruction<State>>::Exec as indexed::IndexOp<operators::Sub>>::Indexed;
+    }
+
+    /// `>+<`, increments the cell `Offset` cells to the right without moving the pointer
+    pub struct IncrAt<Offset, T>(Offset, T);
+    impl<Offset, T: Instruction<State>, State> Instruction<State> for IncrAt<Offset, T>
+    where
+        <T as Instruction<State>>::Exec: indexed::VecOpAt<operators::Add, Offset>,
+    {
+        type Exec =
+            <<T as Instruction<State>>::Exec as indexed::VecOpAt<operators::Add, Offset>>::Indexed;
+    }
+    /// `>-<`, decrements the cell `Offset` cells to the right without moving the pointer
+    pub struct DecrAt<Offset, T>(Offset, T);
+    impl<Offset, T: Instruction<State>, State> Instruction<State> for DecrAt<Offset, T>
+    where
+        <T as Instruction<State>>::Exec: indexed::VecOpAt<operators::Sub, Offset>,
+    {
+        type Exec =
+            <<T as Instruction<State>>::Exec as indexed::VecOpAt<operators::Sub, Offset>>::Indexed;
+    }
+
+    /// `[>]` and its strided variants, moves right by `Stride` until the current cell is zero
+    pub struct ScanRight<Stride, T>(Stride, T);
+    impl<Stride, T: Instruction<State>, State> Instruction<State> for ScanRight<Stride, T>
+    where
+        <T as Instruction<State>>::Exec: indexed::Scan<operators::Add, Stride>,
+    {
+        type Exec =
+            <<T as Instruction<State>>::Exec as indexed::Scan<operators::Add, Stride>>::Indexed;
+    }
+    /// `[<]` and its strided variants, moves left by `Stride` until the current cell is zero
+    pub struct ScanLeft<Stride, T>(Stride, T);
+    impl<Stride, T: Instruction<State>, State> Instruction<State> for ScanLeft<Stride, T>
+    where
+        <T as Instruction<State>>::Exec: indexed::Scan<operators::Sub, Stride>,
+    {
+        type Exec =
+            <<T as Instruction<State>>::Exec as indexed::Scan<operators::Sub, Stride>>::Indexed;
+    }
+
+    pub struct LoopStart<T>(T);
+    // the body starts from whatever state the loop hands it
+    impl<T, State> Instruction<State> for LoopStart<T> {
+        type Exec = State;
+    }
+    pub struct LoopEnd<T>(T);
+    impl<T, State> Instruction<State> for LoopEnd<T>
+    where
+        T: Prefix,
+        <T as Prefix>::Prefix: Instruction<State>,
+        (<<T as Prefix>::Prefix as Instruction<State>>::Exec, T): Loop,
+    {
+        type Exec = <(<<T as Prefix>::Prefix as Instruction<State>>::Exec, T) as Loop>::LoopOut;
+    }
+
+    /// run the loop body `Body` on `T` until the current cell is zero
+    ///
+    /// implemented for `(T, Body)` rather than for every `T`, rustdoc tries each blanket impl on
+    /// every documented type and following the rounds below with unknown types never finishes
+    #[doc(hidden)]
+    pub trait Loop {
+        type LoopOut;
+    }
+    impl<T, Body> Loop for (T, Body)
+    where
+        T: Get,
+        <T as Get>::Val: numbers::Truthy,
+        (T, Body): Rounds<Zero, GetCondition<T>>,
+    {
+        type LoopOut = <(T, Body) as Rounds<Zero, GetCondition<T>>>::LoopOut;
+    }
+
+    // a loop runs in rounds of 1, 2, 4, ... iterations and every round is split in two halves,
+    // so `2^n` iterations only take up `O(n)` levels of the recursion limit rather than `2^n`
+    #[doc(hidden)]
+    pub trait Rounds<Size, Cond> {
+        type LoopOut;
+    }
+    impl<T, Body, Size> Rounds<Size, False> for (T, Body) {
+        type LoopOut = T;
+    }
+    impl<T, Body, Size> Rounds<Size, True> for (T, Body)
+    where
+        (T, Body): Round<Size, True>,
+        RoundOut<Body, Size, T, True>: Get,
+        <RoundOut<Body, Size, T, True> as Get>::Val: numbers::Truthy,
+        (RoundOut<Body, Size, T, True>, Body):
+            Rounds<NextNumber<Size>, GetCondition<RoundOut<Body, Size, T, True>>>,
+    {
+        type LoopOut = <(RoundOut<Body, Size, T, True>, Body) as Rounds<
+            NextNumber<Size>,
+            GetCondition<RoundOut<Body, Size, T, True>>,
+        >>::LoopOut;
+    }
+
+    /// run `Body` on `T` `2^Size` times, stopping early once the current cell is zero
+    #[doc(hidden)]
+    pub trait Round<Size, Cond> {
+        type LoopOut;
+    }
+    type RoundOut<Body, Size, T, Cond> = <(T, Body) as Round<Size, Cond>>::LoopOut;
+    impl<T, Body, Size> Round<Size, False> for (T, Body) {
+        type LoopOut = T;
+    }
+    impl<T, Body> Round<Zero, True> for (T, Body)
+    where
+        T: Iteration,
+        Body: Instruction<Iterated<T>>,
+        IterationOut<Body, T>: numbers::Matches<T, numbers::MatchFuel>,
+        Progress<Body, T>: numbers::Select<Diverges<T>, IterationOut<Body, T>>,
+    {
+        type LoopOut =
+            <Progress<Body, T> as numbers::Select<Diverges<T>, IterationOut<Body, T>>>::Output;
+    }
+    type IterationOut<Body, T> = <Body as Instruction<Iterated<T>>>::Exec;
+    /// `True` if an iteration of `Body` leaves `T` as it was, a change too deep to see within
+    /// `MatchFuel` counts as progress
+    type Progress<Body, T> =
+        <IterationOut<Body, T> as numbers::Matches<T, numbers::MatchFuel>>::Output;
+
+    /// the state a loop iteration starts from, a chance for `stats::Counted` to count it
+    #[doc(hidden)]
+    pub trait Iteration {
+        type Next;
+    }
+    type Iterated<T> = <T as Iteration>::Next;
+    impl<Ram, Output, C, Input> Iteration for brainfuck::InterpreterBase<Ram, Output, C, Input> {
+        type Next = Self;
+    }
+    impl<Index, Val, Tape> Iteration for indexed::Indexed<Index, Val, Tape> {
+        type Next = Self;
+    }
+
+    /// the result of a loop that never terminates since an iteration starting from `State` with
+    /// a non-zero current cell leaves it unchanged
+    ///
+    /// ```compile_fail,E0277
+    /// use brainfuck::{bf, brainfuck::Interpreter, instructions::Execute};
+    /// // `+-` leaves the cell at 1 so the loop never ends, the `.` after it does not compile
+    /// type Code = bf!(Interpreter; +[+-].);
+    /// let _: Execute<Code>;
+    /// ```
+    pub struct Diverges<State>(State);
+    // a nested loop diverged, which is progress as far as the outer loop is concerned
+    impl<State, T, Fuel> numbers::Matches<T, Fuel> for Diverges<State> {
+        type Output = False;
+    }
+    // programs split into chunks continue from the previous state
+    impl<Inner, State> Instruction<State> for Diverges<Inner> {
+        type Exec = Self;
+    }
+    /// never implemented, commands after a diverging loop require it to point at the loop
+    #[diagnostic::on_unimplemented(
+        message = "the program runs forever, an iteration of a loop leaves the state unchanged",
+        label = "executed after the loop"
+    )]
+    pub trait Terminates {}
+    impl<Op, State> indexed::VecOp<Op> for Diverges<State>
+    where
+        Self: Terminates,
+    {
+        type Indexed = Self;
+    }
+    impl<Op, State> indexed::IndexOp<Op> for Diverges<State>
+    where
+        Self: Terminates,
+    {
+        type Indexed = Self;
+    }
+    impl<Op, Offset, State> indexed::VecOpAt<Op, Offset> for Diverges<State>
+    where
+        Self: Terminates,
+    {
+        type Indexed = Self;
+    }
+    impl<State> brainfuck::Print for Diverges<State>
+    where
+        Self: Terminates,
+    {
+        type Out = Self;
+    }
+    impl<State> brainfuck::Read for Diverges<State>
+    where
+        Self: Terminates,
+    {
+        type Out = Self;
+    }
+    impl<State> brainfuck::Debug for Diverges<State>
+    where
+        Self: Terminates,
+    {
+        type Output = Self;
+        type Ram = Self;
+    }
+    impl<T, Body, Size> Round<NextNumber<Size>, True> for (T, Body)
+    where
+        (T, Body): Round<Size, True>,
+        RoundOut<Body, Size, T, True>: Get,
+        <RoundOut<Body, Size, T, True> as Get>::Val: numbers::Truthy,
+        (RoundOut<Body, Size, T, True>, Body):
+            Round<Size, GetCondition<RoundOut<Body, Size, T, True>>>,
+    {
+        type LoopOut = RoundOut<
+            Body,
+            Size,
+            RoundOut<Body, Size, T, True>,
+            GetCondition<RoundOut<Body, Size, T, True>>,
+        >;
+    }
+
+    pub struct Print<T>(T);
+    impl<T: Instruction<State>, State> Instruction<State> for Print<T>
+    where
+        <T as Instruction<State>>::Exec: brainfuck::Print,
+    {
+        type Exec = <<T as Instruction<State>>::Exec as brainfuck::Print>::Out;
+    }
+    /// `,`, replaces the current cell with the next input value
+    pub struct Read<T>(T);
+    impl<T: Instruction<State>, State> Instruction<State> for Read<T>
+    where
+        <T as Instruction<State>>::Exec: brainfuck::Read,
+    {
+        type Exec = <<T as Instruction<State>>::Exec as brainfuck::Read>::Out;
+    }
+
+    /// what an instruction made with `define_instruction!` does to the state it runs on
+    pub trait Command<State> {
+        type Out;
+    }
+    /// defines the instruction `$name<T>` running the `Command` `$command` after `T`, with
+    /// everything it needs to run in loops, in a `step::Machine` and under `brainfuck::Debug`.
+    /// `$command` also becomes a snippet so `bf!` can splice it in as `($command)`
+    ///
+    /// ```
+    /// use brainfuck::{
+    ///     bf,
+    ///     brainfuck::{GetOutput, Interpreter},
+    ///     define_instruction,
+    ///     indexed::{self, Debug, Get},
+    ///     instructions::{Command, Execute},
+    ///     numbers, operators,
+    /// };
+    /// pub struct Doubling;
+    /// type Doubled<State> = <<State as Get>::Val as numbers::Plus<<State as Get>::Val>>::Output;
+    /// impl<State: Get> Command<State> for Doubling
+    /// where
+    ///     <State as Get>::Val: numbers::Plus<<State as Get>::Val>,
+    ///     State: indexed::VecOp<operators::Set<Doubled<State>>>,
+    /// {
+    ///     type Out = <State as indexed::VecOp<operators::Set<Doubled<State>>>>::Indexed;
+    /// }
+    /// define_instruction!(pub Double = Doubling);
+    /// type Code = bf!(Interpreter; +++[>+(Doubling)<-]>.);
+    /// assert_eq!(GetOutput::<Execute<Code>>::data(), [14]);
+    /// ```
+    ///
+    /// a custom instruction has no brainfuck source and no inverse, so programs using one are
+    /// rejected by `reflect::Reflect`, and with it `dot` and `analyze`, and by `Reverse`
+    ///
+    /// ```compile_fail,E0277
+    /// use brainfuck::{
+    ///     bf, brainfuck::Interpreter, define_instruction, indexed, instructions::Command,
+    ///     numbers::Zero, operators, reflect::Reflect,
+    /// };
+    /// pub struct Clearing;
+    /// impl<State: indexed::VecOp<operators::Set<Zero>>> Command<State> for Clearing {
+    ///     type Out = <State as indexed::VecOp<operators::Set<Zero>>>::Indexed;
+    /// }
+    /// define_instruction!(pub Clear = Clearing);
+    /// let _ = <bf!(Interpreter; +(Clearing)) as Reflect>::program();
+    /// ```
+    #[macro_export]
+    macro_rules! define_instruction {
+        ($(#[$attr:meta])* $vis:vis $name:ident = $command:ty) => {
+            $(#[$attr])*
+            $vis struct $name<T>(T);
+            impl<T, State> $crate::instructions::Instruction<State> for $name<T>
+            where
+                T: $crate::instructions::Instruction<State>,
+                $command: $crate::instructions::Command<
+                    <T as $crate::instructions::Instruction<State>>::Exec,
+                >,
+            {
+                type Exec = <$command as $crate::instructions::Command<
+                    <T as $crate::instructions::Instruction<State>>::Exec,
+                >>::Out;
+            }
+            impl<T> $crate::instructions::Snippet<T> for $command {
+                type Chain = $name<T>;
+            }
+            impl<T: $crate::instructions::Prefix> $crate::instructions::Prefix for $name<T> {
+                type Prefix = <T as $crate::instructions::Prefix>::Prefix;
+            }
+            impl<T: $crate::brainfuck::Debug> $crate::brainfuck::Debug for $name<T> {
+                type Output = <T as $crate::brainfuck::Debug>::Output;
+                type Ram = <T as $crate::brainfuck::Debug>::Ram;
+            }
+            impl<T: $crate::TypeNamed> $crate::TypeNamed for $name<T> {
+                fn name() -> String {
+                    format!("{}<{}>", stringify!($name), T::name())
+                }
+            }
+            impl<T, Rest> $crate::step::Flatten<Rest> for $name<T>
+            where
+                T: $crate::step::Flatten<$crate::linked_list::Node<$name<()>, Rest>>,
+            {
+                type Program = <T as $crate::step::Flatten<
+                    $crate::linked_list::Node<$name<()>, Rest>,
+                >>::Program;
+                type Base =
+                    <T as $crate::step::Flatten<$crate::linked_list::Node<$name<()>, Rest>>>::Base;
+            }
+            impl $crate::step::Kind for $name<()> {
+                type Kind = $crate::step::Plain;
+            }
+            impl<State> $crate::step::Apply<State> for $name<()>
+            where
+                $name<State>: $crate::instructions::Instruction<()>,
+            {
+                type Out = <$name<State> as $crate::instructions::Instruction<()>>::Exec;
+            }
+        };
+    }
+
+    /// a snippet type that turns into instructions on top of `T`, see `snippets`
+    pub trait Snippet<T> {
+        type Chain;
+    }
+    /// `(Snippet)` in `bf!`, runs the instructions of the snippet as if they were written out
+    pub struct Splice<S, T>(S, T);
+    impl<S: Snippet<T>, T, State> Instruction<State> for Splice<S, T>
+    where
+        <S as Snippet<T>>::Chain: Instruction<State>,
+    {
+        type Exec = <<S as Snippet<T>>::Chain as Instruction<State>>::Exec;
+    }
+
+    /// the instructions in front of the loop whose body is `Self`
+    pub trait Prefix {
+        type Prefix;
+    }
+    macro_rules! prefix {
+        ($($name:ident),+) => {
+            $(impl<T: Prefix> Prefix for $name<T> {
+                type Prefix = <T as Prefix>::Prefix;
+            })*
+        };
+    }
+    prefix!(Decr, MoveRight, MoveLeft, Incr, Print, Read);
+    impl<Offset, T: Prefix> Prefix for IncrAt<Offset, T> {
+        type Prefix = <T as Prefix>::Prefix;
+    }
+    impl<Offset, T: Prefix> Prefix for DecrAt<Offset, T> {
+        type Prefix = <T as Prefix>::Prefix;
+    }
+    impl<Stride, T: Prefix> Prefix for ScanRight<Stride, T> {
+        type Prefix = <T as Prefix>::Prefix;
+    }
+    impl<Stride, T: Prefix> Prefix for ScanLeft<Stride, T> {
+        type Prefix = <T as Prefix>::Prefix;
+    }
+    // the loops of a snippet are balanced
+    impl<S, T: Prefix> Prefix for Splice<S, T> {
+        type Prefix = <T as Prefix>::Prefix;
+    }
+    impl<T> Prefix for LoopStart<T> {
+        type Prefix = T;
+    }
+    // a nested loop is skipped as a whole
+    impl<T: Prefix> Prefix for LoopEnd<T>
+    where
+        <T as Prefix>::Prefix: Prefix,
+    {
+        type Prefix = <<T as Prefix>::Prefix as Prefix>::Prefix;
+    }
+
+    /// builds the inverse of a loop free chain on top of `Base`, the last command is undone first
+    #[diagnostic::on_unimplemented(
+        message = "only loop free chains of `+`, `-`, `>` and `<` can be reversed",
+        label = "`{Self}` has no inverse",
+        note = "instructions made with `define_instruction!` can not be reversed either"
+    )]
+    pub trait Invert<Base> {
+        type Inverse;
+    }
+    macro_rules! invert {
+        ($($name:ident <-> $inverse:ident),+) => {
+            $(impl<T: Invert<$inverse<Base>>, Base> Invert<Base> for $name<T> {
+                type Inverse = <T as Invert<$inverse<Base>>>::Inverse;
+            })*
+        };
+    }
+    invert!(Incr <-> Decr, Decr <-> Incr, MoveRight <-> MoveLeft, MoveLeft <-> MoveRight);
+    impl<Offset, T: Invert<DecrAt<Offset, Base>>, Base> Invert<Base> for IncrAt<Offset, T> {
+        type Inverse = <T as Invert<DecrAt<Offset, Base>>>::Inverse;
+    }
+    impl<Offset, T: Invert<IncrAt<Offset, Base>>, Base> Invert<Base> for DecrAt<Offset, T> {
+        type Inverse = <T as Invert<IncrAt<Offset, Base>>>::Inverse;
+    }
+    impl<S: Snippet<T>, T, Base> Invert<Base> for Splice<S, T>
+    where
+        <S as Snippet<T>>::Chain: Invert<Base>,
+    {
+        type Inverse = <<S as Snippet<T>>::Chain as Invert<Base>>::Inverse;
+    }
+    // the state the program started from is replaced by the one the inverse runs on
+    impl<Ram, Output, C, Input, Base> Invert<Base>
+        for brainfuck::InterpreterBase<Ram, Output, C, Input>
+    {
+        type Inverse = Base;
+    }
+    impl<Index, Val, Tape, Base> Invert<Base> for indexed::Indexed<Index, Val, Tape> {
+        type Inverse = Base;
+    }
+    /// the inverse of `Program` running on `State`, `Execute<Reverse<P, Execute<P>>>` leaves the
+    /// tape as `P` found it as long as no cell saturates and the pointer is not clamped, cells
+    /// the pointer moved onto stay on the tape as zeros
+    pub type Reverse<Program, State> = <Program as Invert<State>>::Inverse;
+
+    type GetCondition<T> = <<T as indexed::Get>::Val as numbers::Truthy>::Output;
+    pub type Execute<T> = <T as Instruction<()>>::Exec;
+    #[cfg(test)]
+    type GetRam<T> = <T as brainfuck::Debug>::Ram;
+
+    #[cfg(test)]
+    mod tests {
+        use crate::{
+            brainfuck::{self, GetOutput, InterpreterBase},
+            config::{Config, Saturating, Signed},
+            indexed::{Circular, Debug, EmptyIndexed, Get, Indexed, Unbounded},
+            instructions::{
+                Decr, DecrAt, Execute, GetRam, Incr, IncrAt, Instruction, Loop, LoopEnd, LoopStart,
+                MoveLeft, MoveRight, Reverse,
+            },
+            linked_list::{End, Node},
+            numbers::{self, Neg, Nine, One, Pos, Three, Two, Var, Zero},
+            TypeNamed,
+        };
+
+        #[test]
+        fn test_loop() {
+            // memory is init to [3] with pointer at index 0
+            type Ram = list![Three];
+            assert_eq!(Ram::data(), [3]);
+            assert_eq!(Ram::index(), 0);
+            type Ram2 = InterpreterBase<Ram, list![]>;
+            type Code = bf!(Ram2; [-]>+.);
+            //type Code = bf!(Ram; [-]>+);
+            // evaluate the code
+            type Output = GetRam<Execute<Code>>;
+            assert_eq!(Output::data(), [0, 1]);
+            assert_eq!(Output::index(), 1);
+        }
+        #[test]
+        fn test_loop2() {
+            type Ram = InterpreterBase<list![], list![]>;
+            type Code = bf!(Ram; ++[-]+);
+            type Output = GetRam<Execute<Code>>;
+            //assert_eq!(<Output as indexed::Get>::Val::eval(), 0);
+            assert_eq!(Output::data(), [1]);
+        }
+        #[test]
+        fn empty_loop() {
+            type Ram = InterpreterBase<list![Zero, One], list![]>;
+            type Code = bf!(Ram; [>-<]);
+            type Output = GetRam<Execute<Code>>;
+            assert_eq!(Output::data(), [0, 1]);
+        }
+        #[test]
+        fn base_loop_3() {
+            type Ram = InterpreterBase<list![Nine], list![]>;
+            type Body = Decr<MoveLeft<Incr<MoveRight<LoopStart<Ram>>>>>;
+            type Code = bf!(Ram; [>+<-]);
+            assert_eq!(Code::name(), <LoopEnd<Body>>::name());
+            // every iteration runs the same body on the state the previous one left
+            type Iterate<State> = <Body as Instruction<State>>::Exec;
+
+            type Iter1 = Iterate<Ram>;
+            assert_eq!(<Iter1 as brainfuck::Debug>::Ram::data(), [8, 1]);
+
+            type Iter2 = Iterate<Iter1>;
+            assert_eq!(<Iter2 as brainfuck::Debug>::Ram::data(), [7, 2]);
+
+            type Iter3 = Iterate<Iter2>;
+            assert_eq!(<Iter3 as brainfuck::Debug>::Ram::data(), [6, 3]);
+
+            type Iter4 = Iterate<Iter3>;
+            assert_eq!(<Iter4 as brainfuck::Debug>::Ram::data(), [5, 4]);
+
+            type Iter5 = Iterate<Iter4>;
+            assert_eq!(<Iter5 as brainfuck::Debug>::Ram::data(), [4, 5]);
+
+            type Iter6 = Iterate<Iter5>;
+            assert_eq!(<Iter6 as brainfuck::Debug>::Ram::data(), [3, 6]);
+
+            type Iter7 = Iterate<Iter6>;
+            assert_eq!(<Iter7 as brainfuck::Debug>::Ram::data(), [2, 7]);
+
+            type Iter8 = Iterate<Iter7>;
+            assert_eq!(<Iter8 as brainfuck::Debug>::Ram::data(), [1, 8]);
+
+            type Iter9 = Iterate<Iter8>;
+            assert_eq!(<Iter9 as brainfuck::Debug>::Ram::data(), [0, 9]);
+
+            // the loop stops once the current cell is zero
+            type Iter10 = <(Iter9, Body) as Loop>::LoopOut;
+            assert_eq!(<Iter10 as brainfuck::Debug>::Ram::data(), [0, 9]);
+            assert_eq!(<<(Ram, Body) as Loop>::LoopOut as brainfuck::Debug>::Ram::data(), [0, 9]);
+        }
+
+        #[test]
+        fn test_loop_3() {
+            type Ram = InterpreterBase<list![Nine], list![]>;
+            type Code = bf!(Ram; [>+<-]);
+            type Output = Execute<Code>;
+            //panic!("{}", Output::name());
+            assert_eq!(<Output as brainfuck::Debug>::Ram::data(), [0, 9]);
+        }
+
+        #[test]
+        fn nested_loops() {
+            type Ram = brainfuck::Interpreter;
+            type Code = bf!(Ram; ++[>+++[>+<-]<-]>>.<<+++[>+++[>>+<<-]<-]>>>.);
+            assert_eq!(GetOutput::<Execute<Code>>::data(), [6, 9]);
+        }
+        trait Same<T> {}
+        impl<T> Same<T> for T {}
+        #[test]
+        fn long_loop() {
+            type Plus<A, B> = <A as numbers::Plus<B>>::Output;
+            type Ten = Plus<Nine, One>;
+            type Fifty = Plus<Plus<Ten, Ten>, Plus<Plus<Ten, Ten>, Ten>>;
+            type Hundred = Plus<Fifty, Fifty>;
+            type ThreeHundred = Plus<Hundred, Plus<Hundred, Hundred>>;
+            // 300 iterations, more than the recursion limit, the loop runs in rounds that only
+            // take up a logarithmic amount of it
+            type Ram = InterpreterBase<list![ThreeHundred], list![]>;
+            type Moved = <GetRam<Execute<bf!(Ram; [>+<-]>)>> as Get>::Val;
+            // the numbers are too deep to evaluate so they are compared as types
+            fn moved()
+            where
+                Moved: Same<ThreeHundred>,
+            {
+            }
+            moved();
+            assert_eq!(GetRam::<Execute<bf!(Ram; [-])>>::data(), [0]);
+        }
+        #[test]
+        fn output_order() {
+            type Ram = brainfuck::Interpreter;
+            type Code = bf!(Ram; +.+.>+++.);
+            // printing prepends, the output is only reversed when read
+            assert_eq!(
+                <Execute<Code>>::name(),
+                <InterpreterBase<list!(One; Two, Three), list![Three, Two, One]>>::name()
+            );
+            assert_eq!(GetOutput::<Execute<Code>>::data(), [1, 2, 3]);
+        }
+        #[test]
+        fn diverges() {
+            type Ram = brainfuck::Interpreter;
+            const ONE: &str = "Diverges<Interpreter<List<0,[1, ]>, List<0,[]>>>";
+            assert_eq!(<Execute<bf!(Ram; +[])>>::name(), ONE);
+            type Idle = Execute<bf!(Ram; +>+<[><+-])>;
+            assert_eq!(Idle::name(), "Diverges<Interpreter<List<0,[1, 1, ]>, List<0,[]>>>");
+            // the first iteration grows the tape, the second one changes nothing
+            type Grown = Execute<bf!(Ram; +[>-<])>;
+            assert_eq!(Grown::name(), "Diverges<Interpreter<List<0,[1, 0, ]>, List<0,[]>>>");
+            // moving left of cell 0 of an unbounded tape stays there
+            type Pinned = Execute<bf!(Ram; >+<+[<])>;
+            assert_eq!(Pinned::name(), "Diverges<Interpreter<List<0,[1, 1, ]>, List<0,[]>>>");
+            // scans around a circular tape with no zero cell come back to where they started
+            type Ring = brainfuck::Interpreter<Config<Saturating, Circular<Three>>>;
+            const RING: &str = "Diverges<Interpreter<List<2,[1, 1, 1, ]>, List<0,[]>>>";
+            assert_eq!(<Execute<bf!(Ring; +>+>+[<])>>::name(), RING);
+            assert_eq!(<Execute<bf!(Ring; +>+>+[>])>>::name(), RING);
+            type Strided = Execute<bf!(Ring; +>+>+>>[>>])>;
+            assert_eq!(Strided::name(), "Diverges<Interpreter<List<1,[1, 1, 1, ]>, List<0,[]>>>");
+            type Single = brainfuck::Interpreter<Config<Saturating, Circular<One>>>;
+            assert_eq!(<Execute<bf!(Single; +[<])>>::name(), ONE);
+            assert_eq!(<Execute<bf!(Single; +[>])>>::name(), ONE);
+        }
+        #[test]
+        fn terminates() {
+            type Ram = brainfuck::Interpreter;
+            // loops that end are run to the end, however often their state repeats in between
+            type Progress = Execute<bf!(Ram; ++[>+<-]>[<+>-]+[-].)>;
+            assert_eq!(GetOutput::<Progress>::data(), [0]);
+            // a scan on a circular tape wraps around to the zero cell before it
+            type Ring = brainfuck::Interpreter<Config<Saturating, Circular<Three>>>;
+            type Wrapped = Execute<bf!(Ring; +>>+[>]+)>;
+            assert_eq!(GetRam::<Wrapped>::data(), [1, 1, 1]);
+            assert_eq!(GetRam::<Wrapped>::index(), 1);
+        }
+        #[test]
+        fn after_diverges() {
+            type Ram = brainfuck::Interpreter;
+            // loops after a diverging one are skipped, other commands do not compile
+            type Stuck = Execute<bf!(Ram; +[])>;
+            assert_eq!(<Execute<bf!(Stuck; [-][>+<])>>::name(), Stuck::name());
+            // an inner loop diverging ends the loops around it, and later loops are skipped
+            type Nested = Execute<bf!(Ram; ++[>+[]][-])>;
+            assert_eq!(Nested::name(), "Diverges<Interpreter<List<1,[2, 1, ]>, List<0,[]>>>");
+        }
+        #[test]
+        fn glued_tokens() {
+            type Ram = brainfuck::Interpreter;
+            type Code = bf!(Ram; +++...>>>+<<<-->+.<->..);
+            assert_eq!(GetOutput::<Execute<Code>>::data(), [3, 3, 3, 1, 1, 1]);
+            assert_eq!(GetRam::<Execute<Code>>::data(), [0, 1, 0, 1]);
+        }
+        #[test]
+        fn scan() {
+            type Ram = InterpreterBase<list![One, Two, Zero, One, One, Zero, One], list![]>;
+            type Right = bf!(Ram; [>]);
+            type Loop = LoopEnd<MoveRight<LoopStart<Ram>>>;
+            assert_eq!(GetRam::<Execute<Right>>::index(), 2);
+            assert_eq!(GetRam::<Execute<Loop>>::index(), 2);
+            assert_eq!(Right::name(), "ScanR<1, Interpreter<List<0,[1, 2, 0, 1, 1, 0, 1, ]>, List<0,[]>>>");
+
+            type Strided = bf!(Ram; >[>>]);
+            assert_eq!(GetRam::<Execute<Strided>>::index(), 5);
+            type Left = bf!(Ram; >>>>>>[<<]);
+            assert_eq!(GetRam::<Execute<Left>>::index(), 2);
+            type LeftOnZero = bf!(Ram; >>[<]);
+            assert_eq!(GetRam::<Execute<LeftOnZero>>::index(), 2);
+            // out of the bounds of the tape every cell is zero
+            type PastEnd = bf!(Ram; >>>[>]+);
+            assert_eq!(GetRam::<Execute<PastEnd>>::data(), [1, 2, 0, 1, 1, 1, 1]);
+        }
+        // cells are only added to the tape once they are written to, removing `+-` can leave
+        // the tape shorter but those cells read as 0 either way
+        fn trimmed(mut data: Vec<i64>) -> Vec<i64> {
+            while data.last() == Some(&0) {
+                data.pop();
+            }
+            data
+        }
+        // `bf_opt!` has to end up in the same state as `bf!`
+        macro_rules! same_state {
+            ($($code:tt)*) => {{
+                type Plain = Execute<bf!(brainfuck::Interpreter; $($code)*)>;
+                type Optimized = Execute<bf_opt!(brainfuck::Interpreter; $($code)*)>;
+                assert_eq!(
+                    trimmed(GetRam::<Optimized>::data()),
+                    trimmed(GetRam::<Plain>::data())
+                );
+                assert_eq!(GetRam::<Optimized>::index(), GetRam::<Plain>::index());
+                assert_eq!(GetOutput::<Optimized>::data(), GetOutput::<Plain>::data());
+            }};
+        }
+        #[test]
+        fn peephole() {
+            type Ram = brainfuck::Interpreter;
+            same_state!([-.]+-++><[->+<][>.]>++<>--.);
+            same_state!(+[>+-<-]>[-]<<>>+-.);
+            same_state!(-+.<>+.);
+            same_state!(+->>++-<<-[[-]][+]>.);
+
+            assert_eq!(
+                <bf_opt!(brainfuck::Interpreter; [-.<>]+-><<++[-]+[>]-+)>::name(),
+                <bf!(Ram; <++[-]+[>]-+)>::name()
+            );
+            assert_eq!(<bf_opt!(Ram; +[>+-<-])>::name(), <bf!(Ram; +[-])>::name());
+            assert_eq!(
+                <bf_opt!(InterpreterBase<list![], list![]>; [-]+)>::name(),
+                <bf!(InterpreterBase<list![], list![]>; +)>::name()
+            );
+        }
+        #[test]
+        fn peephole_on_a_running_program() {
+            // cell 0 is 3 when the program starts, so its first loop has to run
+            type Ram = Execute<bf!(brainfuck::Interpreter; +++)>;
+            type Optimized = Execute<bf_opt!(Ram; [>+<-])>;
+            assert_eq!(GetRam::<Optimized>::data(), [0, 3]);
+            assert_eq!(GetRam::<Optimized>::data(), GetRam::<Execute<bf!(Ram; [>+<-])>>::data());
+            // an alias may name any state, so it is not assumed to be fresh either
+            type Alias = brainfuck::Interpreter;
+            assert_eq!(<bf_opt!(Alias; [-]+)>::name(), <bf!(Alias; [-]+)>::name());
+        }
+        #[test]
+        fn offsets() {
+            same_state!(+++[->++>+<<]>>.<.);
+            same_state!(++[>+>-<+<-]>>.);
+            same_state!(+>+<<+>->.<.);
+            same_state!(+>>+[<+>-]<+<.);
+            assert_eq!(
+                <bf_opt!(Ram; +>->+<<)>::name(),
+                <IncrAt<Two, DecrAt<One, Incr<Ram>>>>::name()
+            );
+
+            type Ram = brainfuck::Interpreter;
+            assert_eq!(
+                <bf_opt!(Ram; +[->++>+<<])>::name(),
+                <LoopEnd<IncrAt<Two, IncrAt<One, IncrAt<One, Decr<LoopStart<Incr<Ram>>>>>>>>::name()
+            );
+            // moving left of the start is not pointer neutral as the pointer saturates at 0
+            assert_eq!(<bf_opt!(Ram; <+>)>::name(), <bf!(Ram; <+>)>::name());
+        }
+        // running the inverse after the program restores the tape it started from
+        macro_rules! undone {
+            ($ram:ty; $($code:tt)*) => {{
+                type Plain = bf!($ram; $($code)*);
+                type Optimized = bf_opt!($ram; $($code)*);
+                type Undone = Execute<Reverse<Plain, Execute<Plain>>>;
+                type UndoneOptimized = Execute<Reverse<Optimized, Execute<Optimized>>>;
+                assert_eq!(trimmed(GetRam::<Undone>::data()), trimmed(GetRam::<$ram>::data()));
+                assert_eq!(GetRam::<Undone>::index(), GetRam::<$ram>::index());
+                assert_eq!(
+                    trimmed(GetRam::<UndoneOptimized>::data()),
+                    trimmed(GetRam::<$ram>::data())
+                );
+                assert_eq!(GetRam::<UndoneOptimized>::index(), GetRam::<$ram>::index());
+            }};
+        }
+        #[test]
+        fn reverse() {
+            type Ram = InterpreterBase<list![Three, One], list![]>;
+            undone!(Ram; +++>->>++<<<);
+            undone!(Ram; >+>++>+++);
+            undone!(Ram; -->-<+>>>+<<);
+            undone!(brainfuck::Interpreter; +>++>+++<<);
+            assert_eq!(
+                <Reverse<bf!(Ram; +>->), brainfuck::Interpreter>>::name(),
+                <bf!(brainfuck::Interpreter; <+<-)>::name()
+            );
+            // the tape keeps the cells the program moved onto
+            type Moved = bf!(Ram; >>>+);
+            assert_eq!(GetRam::<Execute<Reverse<Moved, Execute<Moved>>>>::data(), [3, 1, 0, 0]);
+        }
+        fn same<T: Same<U>, U>() {}
+        // these only compile if the program does the same to every possible starting tape
+        fn symbolic<A, B>() {
+            // signed cells, a saturating cell could not be decremented below where it started
+            type Cell<N, Offset = Pos<Zero>> = Var<N, Offset, Signed>;
+            type Tape<A, B, Last> = Node<A, Node<B, Node<Last, End<Pos<Zero>>>>>;
+            type Ram<A, B> = InterpreterBase<
+                Indexed<Zero, Node<Cell<A>, Node<Cell<B>, End<Pos<Zero>>>>>,
+                EmptyIndexed,
+                Config<Signed, Unbounded>,
+            >;
+            type Code<A, B> = bf!(Ram<A, B>; >+++<-->>+);
+            type Moved<A, B> =
+                Indexed<Two, Tape<Cell<A, Neg<Two>>, Cell<B, Pos<Three>>, Pos<One>>>;
+            same::<GetRam<Execute<Code<A, B>>>, Moved<A, B>>();
+            // and running the reverse gives back the variables themselves
+            type Undone<A, B> = Execute<Reverse<Code<A, B>, Execute<Code<A, B>>>>;
+            type Restored<A, B> = Indexed<Zero, Tape<Cell<A>, Cell<B>, Pos<Zero>>>;
+            same::<GetRam<Undone<A, B>>, Restored<A, B>>();
+        }
+        #[test]
+        fn symbolic_names() {
+            symbolic::<u8, u16>();
+            struct A;
+            impl TypeNamed for A {
+                fn name() -> String {
+                    "a".to_owned()
+                }
+            }
+            type Ram = InterpreterBase<Indexed<Zero, Node<Var<A>, End>>, EmptyIndexed>;
+            assert_eq!(GetRam::<Execute<bf!(Ram; +++->+)>>::name(), "List<1,[a+2, 1, ]>");
+        }
+    }
+}
+
+pub mod snippets {
+    //! reusable pieces of programs, spliced into `bf!` as `(Snippet)`. they are written with
+    //! `bf!` themselves so a snippet can splice in others
+
+    use crate::{
+        instructions::*,
+        numbers::{NextNumber, Zero},
+    };
+
+    /// `[-]`
+    pub struct Clear;
+    impl<T> Snippet<T> for Clear {
+        type Chain = bf!(T; [-]);
+    }
+    /// `>` `N` times
+    pub struct Right<N>(N);
+    impl<T> Snippet<T> for Right<Zero> {
+        type Chain = T;
+    }
+    impl<N, T> Snippet<T> for Right<NextNumber<N>>
+    where
+        Right<N>: Snippet<MoveRight<T>>,
+    {
+        type Chain = <Right<N> as Snippet<MoveRight<T>>>::Chain;
+    }
+    /// `<` `N` times
+    pub struct Left<N>(N);
+    impl<T> Snippet<T> for Left<Zero> {
+        type Chain = T;
+    }
+    impl<N, T> Snippet<T> for Left<NextNumber<N>>
+    where
+        Left<N>: Snippet<MoveLeft<T>>,
+    {
+        type Chain = <Left<N> as Snippet<MoveLeft<T>>>::Chain;
+    }
+    /// adds the current cell to the one `N` cells to the right, leaving the current cell at 0
+    pub struct MoveTo<N>(N);
+    impl<N, T> Snippet<T> for MoveTo<N> {
+        type Chain = bf!(T; [-(Right<N>)+(Left<N>)]);
+    }
+    /// adds the current cell to the one `A` cells to the right, the cell `Temp` cells to the
+    /// right has to be 0 and is used to restore the current cell
+    pub struct CopyTo<A, Temp>(A, Temp);
+    impl<A, Temp, T> Snippet<T> for CopyTo<A, Temp> {
+        type Chain = bf!(T;
+            [-(Right<A>)+(Left<A>)(Right<Temp>)+(Left<Temp>)]
+            (Right<Temp>)[-(Left<Temp>)+(Right<Temp>)](Left<Temp>)
+        );
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::{Clear, CopyTo, Left, MoveTo, Right};
+        use crate::{
+            brainfuck::{self, GetOutput},
+            indexed::Debug,
+            instructions::*,
+            numbers::{One, Three, Two},
+            reflect::{self, Reflect},
+            TypeNamed,
+        };
+
+        type Ram = brainfuck::Interpreter;
+        type GetRam<T> = <T as brainfuck::Debug>::Ram;
+        #[test]
+        fn spliced() {
+            assert_eq!(<bf!(Ram; +(Clear)+)>::name(), <bf!(Ram; +[-]+)>::name());
+            assert_eq!(<bf!(Ram; (Right<Three>)+(Left<Two>))>::name(), <bf!(Ram; >>>+<<)>::name());
+            assert_eq!(reflect::source(&<bf!(Ram; +(MoveTo<Two>))>::program()), "+[->>+<<]");
+
+            type Moved = Execute<bf!(Ram; +++(MoveTo<Two>)>>.)>;
+            assert_eq!(GetRam::<Moved>::data(), [0, 0, 3]);
+            assert_eq!(GetOutput::<Moved>::data(), [3]);
+            type Copied = Execute<bf!(Ram; ++++(CopyTo<One, Two>).>.)>;
+            assert_eq!(GetRam::<Copied>::data(), [4, 4, 0]);
+            assert_eq!(GetOutput::<Copied>::data(), [4, 4]);
+        }
+        #[test]
+        fn inspected() {
+            // a spliced program shows the state it starts from, like any other instruction chain
+            type Base = Execute<bf!(Ram; ++)>;
+            assert_eq!(GetRam::<bf!(Base; +(Clear))>::data(), [2]);
+            assert_eq!(GetOutput::<bf!(Base; (MoveTo<One>).)>::data(), []);
+            assert_eq!(GetRam::<Execute<bf!(Base; +(Clear))>>::data(), [0]);
+        }
+        #[test]
+        fn composes() {
+            // snippets run inside loops like any other command
+            type Looped = Execute<bf!(Ram; ++[>+++(MoveTo<One>)<-](Clear))>;
+            assert_eq!(GetRam::<Looped>::data(), [0, 0, 6]);
+            // and can be built out of other snippets
+            struct Twice<S>(S);
+            impl<S, T> Snippet<T> for Twice<S> {
+                type Chain = bf!(T; (S)(S));
+            }
+            type Doubled = bf!(Ram; (Twice<Twice<Right<One>>>)+(Twice<Clear>));
+            assert_eq!(<Doubled>::name(), <bf!(Ram; >>>>+[-][-])>::name());
+            // a snippet without loops can be reversed
+            type Code = bf!(Ram; +(Twice<Right<Two>>)++);
+            type Undone = Execute<Reverse<Code, Execute<Code>>>;
+            assert_eq!(GetRam::<Execute<Code>>::data(), [1, 0, 0, 0, 2]);
+            assert_eq!(GetRam::<Undone>::data(), [0, 0, 0, 0, 0]);
+            assert_eq!(GetRam::<Undone>::index(), 0);
+        }
+    }
+}
+
+pub mod step {
+    //! small-step evaluation, a `Machine` runs one command of its program per `Step`
+
+    use crate::{
+        brainfuck,
+        indexed::{self, Get},
+        instructions::{self, Instruction},
+        linked_list::{End, Node},
+        numbers::{self, False, NextNumber, True, Zero},
+        stats,
+    };
+
+    /// `Done` holds the commands before the program counter `Pc` newest first and `Todo` the
+    /// ones from `Pc` on, `State` is an interpreter like `brainfuck::Interpreter`
+    pub struct Machine<Done, Todo, Pc, State>(Done, Todo, Pc, State);
+    /// the machine about to run the first command of the instruction chain `Code`
+    pub type Load<Code> =
+        Machine<End, <Code as Flatten<End>>::Program, Zero, <Code as Flatten<End>>::Base>;
+
+    pub trait Inspect {
+        type Pc;
+        type State;
+    }
+    impl<Done, Todo, Pc, State> Inspect for Machine<Done, Todo, Pc, State> {
+        type Pc = Pc;
+        type State = State;
+    }
+    impl<Done, Todo, Pc, State> brainfuck::Debug for Machine<Done, Todo, Pc, State>
+    where
+        State: brainfuck::Debug,
+    {
+        type Output = <State as brainfuck::Debug>::Output;
+        type Ram = <State as brainfuck::Debug>::Ram;
+    }
+
+    /// an instruction chain as a list of commands in program order in front of `Rest`, every
+    /// command is its instruction applied to `()`
+    pub trait Flatten<Rest> {
+        type Program;
+        type Base;
+    }
+    impl<Ram, Output, C, Input, Rest> Flatten<Rest>
+        for brainfuck::InterpreterBase<Ram, Output, C, Input>
+    {
+        type Program = Rest;
+        type Base = Self;
+    }
+    impl<Idx, FirstNode, Tape, Rest> Flatten<Rest> for indexed::Indexed<Idx, FirstNode, Tape> {
+        type Program = Rest;
+        type Base = Self;
+    }
+    impl<State, Stats, Rest> Flatten<Rest> for stats::Counted<State, Stats> {
+        type Program = Rest;
+        type Base = Self;
+    }
+    impl<S: instructions::Snippet<T>, T, Rest> Flatten<Rest> for instructions::Splice<S, T>
+    where
+        <S as instructions::Snippet<T>>::Chain: Flatten<Rest>,
+    {
+        type Program = <<S as instructions::Snippet<T>>::Chain as Flatten<Rest>>::Program;
+        type Base = <<S as instructions::Snippet<T>>::Chain as Flatten<Rest>>::Base;
+    }
+    /// whether a command opens a loop, closes one or is neither
+    pub trait Kind {
+        type Kind;
+    }
+    pub struct Open;
+    pub struct Close;
+    pub struct Plain;
+    impl Kind for instructions::LoopStart<()> {
+        type Kind = Open;
+    }
+    impl Kind for instructions::LoopEnd<()> {
+        type Kind = Close;
+    }
+    /// run a command that is not a bracket on `State`
+    pub trait Apply<State> {
+        type Out;
+    }
+    macro_rules! commands {
+        ($($name:ident$(<$param:ident>)?),*) => {
+            $(impl<$($param,)? T, Rest> Flatten<Rest> for instructions::$name<$($param,)? T>
+            where
+                T: Flatten<Node<instructions::$name<$($param,)? ()>, Rest>>,
+            {
+                type Program =
+                    <T as Flatten<Node<instructions::$name<$($param,)? ()>, Rest>>>::Program;
+                type Base = <T as Flatten<Node<instructions::$name<$($param,)? ()>, Rest>>>::Base;
+            })*
+        };
+    }
+    macro_rules! plain {
+        ($($name:ident$(<$param:ident>)?),*) => {
+            commands!($($name$(<$param>)?),*);
+            $(impl<$($param)?> Kind for instructions::$name<$($param,)? ()> {
+                type Kind = Plain;
+            }
+            impl<$($param,)? State> Apply<State> for instructions::$name<$($param,)? ()>
+            where
+                instructions::$name<$($param,)? State>: Instruction<()>,
+            {
+                type Out = <instructions::$name<$($param,)? State> as Instruction<()>>::Exec;
+            })*
+        };
+    }
+    commands!(LoopStart, LoopEnd);
+    plain!(Incr, Decr, MoveRight, MoveLeft, Print, Read, IncrAt<Offset>, DecrAt<Offset>,
+        ScanRight<Stride>, ScanLeft<Stride>);
+
+    /// run one command, a finished machine stays as it is
+    pub trait Step {
+        type Next;
+    }
+    /// `Step` `N` times
+    pub trait StepN<N> {
+        type Out;
+    }
+    impl<M> StepN<Zero> for M {
+        type Out = M;
+    }
+    impl<M: Step, N> StepN<NextNumber<N>> for M
+    where
+        <M as Step>::Next: StepN<N>,
+    {
+        type Out = <<M as Step>::Next as StepN<N>>::Out;
+    }
+
+    type Cond<State> = <<State as Get>::Val as numbers::Truthy>::Output;
+    /// `Self` with the current command done
+    type Advance<Done, Cmd, Rest, Pc, State> =
+        Machine<Node<Cmd, Done>, Rest, NextNumber<Pc>, State>;
+
+    impl<Done, Blank, Pc, State> Step for Machine<Done, End<Blank>, Pc, State> {
+        type Next = Self;
+    }
+    impl<Done, Cmd: Kind, Rest, Pc, State> Step for Machine<Done, Node<Cmd, Rest>, Pc, State>
+    where
+        Self: StepOn<<Cmd as Kind>::Kind>,
+    {
+        type Next = <Self as StepOn<<Cmd as Kind>::Kind>>::Next;
+    }
+    #[doc(hidden)]
+    pub trait StepOn<Kind> {
+        type Next;
+    }
+    impl<Done, Cmd, Rest, Pc, State> StepOn<Plain> for Machine<Done, Node<Cmd, Rest>, Pc, State>
+    where
+        Cmd: Apply<State>,
+    {
+        type Next = Advance<Done, Cmd, Rest, Pc, <Cmd as Apply<State>>::Out>;
+    }
+    // `[` on a zero cell jumps past its `]`
+    impl<Done, Cmd, Rest, Pc, State> StepOn<Open> for Machine<Done, Node<Cmd, Rest>, Pc, State>
+    where
+        State: Get,
+        <State as Get>::Val: numbers::Truthy,
+        Self: Enter<Cond<State>>,
+    {
+        type Next = <Self as Enter<Cond<State>>>::Next;
+    }
+    #[doc(hidden)]
+    pub trait Enter<Cond> {
+        type Next;
+    }
+    impl<Done, Cmd, Rest, Pc, State> Enter<True> for Machine<Done, Node<Cmd, Rest>, Pc, State> {
+        type Next = Advance<Done, Cmd, Rest, Pc, State>;
+    }
+    impl<Done, Cmd, Rest, Pc, State> Enter<False> for Machine<Done, Node<Cmd, Rest>, Pc, State>
+    where
+        Advance<Done, Cmd, Rest, Pc, State>: Skip<Zero>,
+    {
+        type Next = <Advance<Done, Cmd, Rest, Pc, State> as Skip<Zero>>::Next;
+    }
+    // `]` on a non-zero cell jumps back to just after its `[`
+    impl<Done, Cmd, Rest, Pc, State> StepOn<Close> for Machine<Done, Node<Cmd, Rest>, Pc, State>
+    where
+        State: Get,
+        <State as Get>::Val: numbers::Truthy,
+        Self: Repeat<Cond<State>>,
+    {
+        type Next = <Self as Repeat<Cond<State>>>::Next;
+    }
+    #[doc(hidden)]
+    pub trait Repeat<Cond> {
+        type Next;
+    }
+    impl<Done, Cmd, Rest, Pc, State> Repeat<False> for Machine<Done, Node<Cmd, Rest>, Pc, State> {
+        type Next = Advance<Done, Cmd, Rest, Pc, State>;
+    }
+    impl<Done, Todo, Pc, State> Repeat<True> for Machine<Done, Todo, Pc, State>
+    where
+        Self: Rewind<Zero>,
+    {
+        type Next = <Self as Rewind<Zero>>::Next;
+    }
+
+    /// move forward past the `]` closing the loop `Depth` levels up
+    #[doc(hidden)]
+    pub trait Skip<Depth> {
+        type Next;
+    }
+    impl<Done, Cmd: Kind, Rest, Pc, State, Depth> Skip<Depth>
+        for Machine<Done, Node<Cmd, Rest>, Pc, State>
+    where
+        Self: SkipOn<<Cmd as Kind>::Kind, Depth>,
+    {
+        type Next = <Self as SkipOn<<Cmd as Kind>::Kind, Depth>>::Next;
+    }
+    #[doc(hidden)]
+    pub trait SkipOn<Kind, Depth> {
+        type Next;
+    }
+    impl<Done, Cmd, Rest, Pc, State, Depth> SkipOn<Plain, Depth>
+        for Machine<Done, Node<Cmd, Rest>, Pc, State>
+    where
+        Advance<Done, Cmd, Rest, Pc, State>: Skip<Depth>,
+    {
+        type Next = <Advance<Done, Cmd, Rest, Pc, State> as Skip<Depth>>::Next;
+    }
+    impl<Done, Cmd, Rest, Pc, State, Depth> SkipOn<Open, Depth>
+        for Machine<Done, Node<Cmd, Rest>, Pc, State>
+    where
+        Advance<Done, Cmd, Rest, Pc, State>: Skip<NextNumber<Depth>>,
+    {
+        type Next = <Advance<Done, Cmd, Rest, Pc, State> as Skip<NextNumber<Depth>>>::Next;
+    }
+    impl<Done, Cmd, Rest, Pc, State> SkipOn<Close, Zero>
+        for Machine<Done, Node<Cmd, Rest>, Pc, State>
+    {
+        type Next = Advance<Done, Cmd, Rest, Pc, State>;
+    }
+    impl<Done, Cmd, Rest, Pc, State, Depth> SkipOn<Close, NextNumber<Depth>>
+        for Machine<Done, Node<Cmd, Rest>, Pc, State>
+    where
+        Advance<Done, Cmd, Rest, Pc, State>: Skip<Depth>,
+    {
+        type Next = <Advance<Done, Cmd, Rest, Pc, State> as Skip<Depth>>::Next;
+    }
+
+    /// move back to just after the `[` opening the loop `Depth` levels up
+    #[doc(hidden)]
+    pub trait Rewind<Depth> {
+        type Next;
+    }
+    impl<Prev: Kind, Done, Todo, Pc, State, Depth> Rewind<Depth>
+        for Machine<Node<Prev, Done>, Todo, NextNumber<Pc>, State>
+    where
+        Self: RewindOn<<Prev as Kind>::Kind, Depth>,
+    {
+        type Next = <Self as RewindOn<<Prev as Kind>::Kind, Depth>>::Next;
+    }
+    #[doc(hidden)]
+    pub trait RewindOn<Kind, Depth> {
+        type Next;
+    }
+    /// `Self` with the previous command undone
+    type Back<Prev, Done, Todo, Pc, State> = Machine<Done, Node<Prev, Todo>, Pc, State>;
+    impl<Prev, Done, Todo, Pc, State, Depth> RewindOn<Plain, Depth>
+        for Machine<Node<Prev, Done>, Todo, NextNumber<Pc>, State>
+    where
+        Back<Prev, Done, Todo, Pc, State>: Rewind<Depth>,
+    {
+        type Next = <Back<Prev, Done, Todo, Pc, State> as Rewind<Depth>>::Next;
+    }
+    impl<Prev, Done, Todo, Pc, State, Depth> RewindOn<Close, Depth>
+        for Machine<Node<Prev, Done>, Todo, NextNumber<Pc>, State>
+    where
+        Back<Prev, Done, Todo, Pc, State>: Rewind<NextNumber<Depth>>,
+    {
+        type Next = <Back<Prev, Done, Todo, Pc, State> as Rewind<NextNumber<Depth>>>::Next;
+    }
+    impl<Prev, Done, Todo, Pc, State> RewindOn<Open, Zero>
+        for Machine<Node<Prev, Done>, Todo, NextNumber<Pc>, State>
+    {
+        type Next = Self;
+    }
+    impl<Prev, Done, Todo, Pc, State, Depth> RewindOn<Open, NextNumber<Depth>>
+        for Machine<Node<Prev, Done>, Todo, NextNumber<Pc>, State>
+    where
+        Back<Prev, Done, Todo, Pc, State>: Rewind<Depth>,
+    {
+        type Next = <Back<Prev, Done, Todo, Pc, State> as Rewind<Depth>>::Next;
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::{Inspect, Load, Step, StepN};
+        use crate::{
+            brainfuck::{Interpreter, InterpreterBase, Snapshot},
+            instructions::*,
+            numbers::{Five, Nine, Number, Plus, Six},
+        };
+
+        fn pc<M: Inspect>() -> i64
+        where
+            <M as Inspect>::Pc: Number,
+        {
+            <<M as Inspect>::Pc as Number>::eval()
+        }
+        type Next<M> = <M as Step>::Next;
+
+        #[test]
+        fn one_command_at_a_time() {
+            type Start = Load<bf!(Interpreter; +[-]>)>;
+            assert_eq!(pc::<Start>(), 0);
+            type S1 = Next<Start>;
+            assert_eq!((pc::<S1>(), Snapshot::of::<S1>().tape), (1, vec![1]));
+            type S2 = Next<S1>;
+            assert_eq!(pc::<S2>(), 2);
+            type S3 = Next<S2>;
+            assert_eq!((pc::<S3>(), Snapshot::of::<S3>().tape), (3, vec![0]));
+            type S4 = Next<S3>;
+            assert_eq!(pc::<S4>(), 4);
+            type S5 = Next<S4>;
+            assert_eq!((pc::<S5>(), Snapshot::of::<S5>().pointer), (5, 1));
+            // a finished machine stays finished
+            assert_eq!(pc::<Next<S5>>(), 5);
+        }
+        #[test]
+        fn jumps() {
+            // `[` on zero skips the whole loop, nested ones included
+            type Skipped = Next<Load<bf!(Interpreter; [+[-]+]>)>>;
+            assert_eq!(pc::<Skipped>(), 7);
+
+            type Ram = InterpreterBase<list![Nine], list![]>;
+            type Start = Load<bf!(Ram; [>+<-])>;
+            // `[` and the body, then `]` jumps back to the first command of the body
+            type Iter1 = <Start as StepN<Six>>::Out;
+            assert_eq!((pc::<Iter1>(), Snapshot::of::<Iter1>().tape), (1, vec![8, 1]));
+            type Iter2 = <Iter1 as StepN<Five>>::Out;
+            assert_eq!((pc::<Iter2>(), Snapshot::of::<Iter2>().tape), (1, vec![7, 2]));
+        }
+        #[test]
+        fn same_as_execute() {
+            type Eighteen = <Nine as Plus<Nine>>::Output;
+            type Code = bf!(Interpreter; ++[>+<-]>.);
+            type Stepped = <Load<Code> as StepN<Eighteen>>::Out;
+            assert_eq!(Snapshot::of::<Stepped>(), Snapshot::of::<Execute<Code>>());
+
+            type Optimized = bf_opt!(Interpreter; +>+>+<<[>]+);
+            type Scanned = <Load<Optimized> as StepN<Nine>>::Out;
+            assert_eq!(Snapshot::of::<Scanned>(), Snapshot::of::<Execute<Optimized>>());
+        }
+    }
+}
+
+pub mod stats {
+    //! opt in execution statistics, wrap the starting state in `Count` and read them from the
+    //! final state through `Statistics`
+
+    use crate::{
+        brainfuck,
+        indexed::{self, Get},
+        instructions,
+        numbers::{self, NextNumber, Number, Zero},
+        operators,
+    };
+
+    /// `State` and what happened to it so far
+    pub struct Counted<State, Stats>(State, Stats);
+    pub struct Stats<Increments, Decrements, Moves, Prints, Iterations, MaxCell, MaxPointer>(
+        Increments,
+        Decrements,
+        Moves,
+        Prints,
+        Iterations,
+        MaxCell,
+        MaxPointer,
+    );
+    /// start counting on `State`
+    pub type Count<State> = Counted<State, Stats<Zero, Zero, Zero, Zero, Zero, Zero, Zero>>;
+
+    /// the counters are numbers so they can be read with `Number::eval` or as consts
+    pub trait Statistics {
+        type Increments: Number;
+        type Decrements: Number;
+        /// moves in either direction, including the ones made by scans
+        type Moves: Number;
+        type Prints: Number;
+        type Iterations: Number;
+        /// the furthest any cell got from zero, in either direction
+        type MaxCell: Number;
+        type MaxPointer: Number;
+        const INCREMENTS: i64 = <Self::Increments as Number>::VALUE;
+        const DECREMENTS: i64 = <Self::Decrements as Number>::VALUE;
+        const MOVES: i64 = <Self::Moves as Number>::VALUE;
+        const PRINTS: i64 = <Self::Prints as Number>::VALUE;
+        const ITERATIONS: i64 = <Self::Iterations as Number>::VALUE;
+        const MAX_CELL: i64 = <Self::MaxCell as Number>::VALUE;
+        const MAX_POINTER: i64 = <Self::MaxPointer as Number>::VALUE;
+    }
+    impl<State, I, D, M, P, L, C, X> Statistics for Counted<State, Stats<I, D, M, P, L, C, X>>
+    where
+        I: Number,
+        D: Number,
+        M: Number,
+        P: Number,
+        L: Number,
+        C: Number,
+        X: Number,
+    {
+        type Increments = I;
+        type Decrements = D;
+        type Moves = M;
+        type Prints = P;
+        type Iterations = L;
+        type MaxCell = C;
+        type MaxPointer = X;
+    }
+
+    /// record writing to a cell with `Op`, leaving it at `Val`
+    pub trait OnWrite<Op, Val> {
+        type Stats;
+    }
+    type Larger<C, Val> = <C as numbers::Max<<Val as numbers::Magnitude>::Output>>::Output;
+    impl<I, D, M, P, L, C, X, Val> OnWrite<operators::Add, Val> for Stats<I, D, M, P, L, C, X>
+    where
+        Val: numbers::Magnitude,
+        C: numbers::Max<<Val as numbers::Magnitude>::Output>,
+    {
+        type Stats = Stats<NextNumber<I>, D, M, P, L, Larger<C, Val>, X>;
+    }
+    // decrementing can make a cell larger too, wrapping below zero or going further negative
+    impl<I, D, M, P, L, C, X, Val> OnWrite<operators::Sub, Val> for Stats<I, D, M, P, L, C, X>
+    where
+        Val: numbers::Magnitude,
+        C: numbers::Max<<Val as numbers::Magnitude>::Output>,
+    {
+        type Stats = Stats<I, NextNumber<D>, M, P, L, Larger<C, Val>, X>;
+    }
+    // reading input
+    impl<I, D, M, P, L, C, X, V, Val> OnWrite<operators::Set<V>, Val> for Stats<I, D, M, P, L, C, X>
+    where
+        Val: numbers::Magnitude,
+        C: numbers::Max<<Val as numbers::Magnitude>::Output>,
+    {
+        type Stats = Stats<I, D, M, P, L, Larger<C, Val>, X>;
+    }
+
+    type Written<State, Op> = <<State as indexed::VecOp<Op>>::Indexed as Get>::Val;
+    impl<State, Stats, Op> indexed::VecOp<Op> for Counted<State, Stats>
+    where
+        State: indexed::VecOp<Op>,
+        <State as indexed::VecOp<Op>>::Indexed: Get,
+        Stats: OnWrite<Op, Written<State, Op>>,
+    {
+        type Indexed = Counted<
+            <State as indexed::VecOp<Op>>::Indexed,
+            <Stats as OnWrite<Op, Written<State, Op>>>::Stats,
+        >;
+    }
+    type WrittenAt<State, Op, Offset> =
+        <<State as indexed::VecOpAt<Op, Offset>>::Indexed as indexed::GetAt<Offset>>::Val;
+    impl<State, Stats, Op, Offset> indexed::VecOpAt<Op, Offset> for Counted<State, Stats>
+    where
+        State: indexed::VecOpAt<Op, Offset>,
+        <State as indexed::VecOpAt<Op, Offset>>::Indexed: indexed::GetAt<Offset>,
+        Stats: OnWrite<Op, WrittenAt<State, Op, Offset>>,
+    {
+        type Indexed = Counted<
+            <State as indexed::VecOpAt<Op, Offset>>::Indexed,
+            <Stats as OnWrite<Op, WrittenAt<State, Op, Offset>>>::Stats,
+        >;
+    }
+    type Moved<State, Op> = <<State as indexed::IndexOp<Op>>::Indexed as indexed::Position>::Idx;
+    impl<State, I, D, M, P, L, C, X, Op> indexed::IndexOp<Op>
+        for Counted<State, Stats<I, D, M, P, L, C, X>>
+    where
+        State: indexed::IndexOp<Op>,
+        <State as indexed::IndexOp<Op>>::Indexed: indexed::Position,
+        X: numbers::Max<Moved<State, Op>>,
+    {
+        type Indexed = Counted<
+            <State as indexed::IndexOp<Op>>::Indexed,
+            Stats<I, D, NextNumber<M>, P, L, C, <X as numbers::Max<Moved<State, Op>>>::Output>,
+        >;
+    }
+    impl<State, I, D, M, P, L, C, X> brainfuck::Print for Counted<State, Stats<I, D, M, P, L, C, X>>
+    where
+        State: brainfuck::Print,
+    {
+        type Out =
+            Counted<<State as brainfuck::Print>::Out, Stats<I, D, M, NextNumber<P>, L, C, X>>;
+    }
+    type Read<State> = <<State as brainfuck::Read>::Out as Get>::Val;
+    impl<State, Stats> brainfuck::Read for Counted<State, Stats>
+    where
+        State: brainfuck::Read,
+        <State as brainfuck::Read>::Out: Get,
+        Stats: OnWrite<operators::Set<()>, Read<State>>,
+    {
+        type Out = Counted<
+            <State as brainfuck::Read>::Out,
+            <Stats as OnWrite<operators::Set<()>, Read<State>>>::Stats,
+        >;
+    }
+    impl<State, I, D, M, P, L, C, X> instructions::Iteration
+        for Counted<State, Stats<I, D, M, P, L, C, X>>
+    {
+        type Next = Counted<State, Stats<I, D, M, P, NextNumber<L>, C, X>>;
+    }
+
+    impl<State: indexed::Position, Stats> indexed::Position for Counted<State, Stats> {
+        type Idx = <State as indexed::Position>::Idx;
+    }
+    impl<State: Get, Stats> Get for Counted<State, Stats> {
+        type Val = <State as Get>::Val;
+    }
+    impl<State: brainfuck::Debug, Stats> brainfuck::Debug for Counted<State, Stats> {
+        type Output = <State as brainfuck::Debug>::Output;
+        type Ram = <State as brainfuck::Debug>::Ram;
+    }
+    impl<State, Stats, S> instructions::Instruction<S> for Counted<State, Stats> {
+        type Exec = Self;
+    }
+    impl<State, Stats, Base> instructions::Invert<Base> for Counted<State, Stats> {
+        type Inverse = Base;
+    }
+    // the statistics always change, whether a loop made progress is up to the state
+    impl<S1, S2, Stats1, Stats2, Fuel> numbers::Matches<Counted<S2, Stats2>, Fuel>
+        for Counted<S1, Stats1>
+    where
+        S1: numbers::Matches<S2, Fuel>,
+    {
+        type Output = <S1 as numbers::Matches<S2, Fuel>>::Output;
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::{Count, Statistics};
+        use crate::{
+            brainfuck::{GetOutput, Interpreter, SignedInterpreter},
+            config,
+            indexed::{self, Debug},
+            instructions::*,
+            numbers::{Nine, Number},
+        };
+
+        #[test]
+        fn counts() {
+            type Final = Execute<bf!(Count<Interpreter>; ++[>+++<-]>.)>;
+            assert_eq!(GetOutput::<Final>::data(), [6]);
+            assert_eq!(
+                [
+                    Final::INCREMENTS,
+                    Final::DECREMENTS,
+                    Final::MOVES,
+                    Final::PRINTS,
+                    Final::ITERATIONS,
+                    Final::MAX_CELL,
+                    Final::MAX_POINTER,
+                ],
+                [8, 2, 5, 1, 2, 6, 1]
+            );
+            assert_eq!(<<Final as Statistics>::Increments as Number>::eval(), 8);
+            // the optimized program does the same work with fewer moves
+            type Optimized = Execute<bf_opt!(Count<Interpreter>; ++[>+++<-]>.)>;
+            assert_eq!([Optimized::INCREMENTS, Optimized::MOVES], [8, 1]);
+            assert_eq!(Optimized::MAX_CELL, 6);
+        }
+        #[test]
+        fn const_context() {
+            // the optimizer leaves only the moves of the scan and the final `<`
+            type Final = Execute<bf_opt!(Count<SignedInterpreter>; ->+>+<<[>]<.)>;
+            const MOVES: i64 = <Final as Statistics>::MOVES;
+            const MAX_POINTER: i64 = <Final as Statistics>::MAX_POINTER;
+            assert_eq!((MOVES, MAX_POINTER), (4, 3));
+            const _: () = assert!(<Final as Statistics>::MAX_CELL == 1);
+        }
+        #[test]
+        fn reads() {
+            type Ram = Count<Interpreter<config::Default, ll!(Nine)>>;
+            type Final = Execute<bf!(Ram; ,[-]+)>;
+            assert_eq!([Final::MAX_CELL, Final::DECREMENTS, Final::ITERATIONS], [9, 9, 9]);
+        }
+        #[test]
+        fn wrapping_decrement() {
+            type Ram = Count<Interpreter<config::Config<config::Wrap8, indexed::Unbounded>>>;
+            type Final = Execute<bf!(Ram; -.)>;
+            assert_eq!(GetOutput::<Final>::data(), [255]);
+            assert_eq!(Final::MAX_CELL, 255);
+        }
+        #[test]
+        fn negative_cells() {
+            type Final = Execute<bf!(Count<SignedInterpreter>; ---++)>;
+            assert_eq!([Final::MAX_CELL, Final::DECREMENTS], [3, 3]);
+        }
+    }
+}
+
+pub mod reflect {
+    //! the program behind an instruction type as a runtime value
+
+    use crate::{
+        brainfuck::{self, Snapshot},
+        indexed, instructions,
+        numbers::Number,
+        stats,
+    };
+    use std::fmt;
+
+    /// a single command, loops hold their body
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum Op {
+        Incr,
+        Decr,
+        Right,
+        Left,
+        IncrAt(usize),
+        DecrAt(usize),
+        ScanRight(usize),
+        ScanLeft(usize),
+        Print,
+        Read,
+        Loop(Vec<Op>),
+    }
+
+    #[diagnostic::on_unimplemented(
+        message = "`{Self}` is not made of brainfuck commands",
+        label = "can not be turned back into commands",
+        note = "instructions made with `define_instruction!` have no brainfuck source"
+    )]
+    pub trait Reflect {
+        /// push the commands of `Self` onto `bodies`, the last body is the innermost open loop
+        fn reflect(bodies: &mut Vec<Vec<Op>>);
+        fn program() -> Vec<Op> {
+            let mut bodies = vec![vec![]];
+            Self::reflect(&mut bodies);
+            assert_eq!(bodies.len(), 1, "unbalanced loops");
+            bodies.pop().unwrap()
+        }
+    }
+    fn push(bodies: &mut [Vec<Op>], op: Op) {
+        bodies.last_mut().expect("unbalanced loops").push(op);
+    }
+    impl<Ram, Output, C, Input> Reflect for brainfuck::InterpreterBase<Ram, Output, C, Input> {
+        fn reflect(_: &mut Vec<Vec<Op>>) {}
+    }
+    impl<Idx, FirstNode, Tape> Reflect for indexed::Indexed<Idx, FirstNode, Tape> {
+        fn reflect(_: &mut Vec<Vec<Op>>) {}
+    }
+    impl<State, Stats> Reflect for stats::Counted<State, Stats> {
+        fn reflect(_: &mut Vec<Vec<Op>>) {}
+    }
+    macro_rules! reflect {
+        ($($name:ident => $op:ident),*) => {
+            $(impl<T: Reflect> Reflect for instructions::$name<T> {
+                fn reflect(bodies: &mut Vec<Vec<Op>>) {
+                    T::reflect(bodies);
+                    push(bodies, Op::$op);
+                }
+            })*
+        };
+        ($($name:ident => $op:ident($param:ident)),*) => {
+            $(impl<$param: Number, T: Reflect> Reflect for instructions::$name<$param, T> {
+                fn reflect(bodies: &mut Vec<Vec<Op>>) {
+                    T::reflect(bodies);
+                    push(bodies, Op::$op($param::eval() as usize));
+                }
+            })*
+        };
+    }
+    reflect!(Incr => Incr, Decr => Decr, MoveRight => Right, MoveLeft => Left, Print => Print,
+        Read => Read);
+    reflect!(IncrAt => IncrAt(Offset), DecrAt => DecrAt(Offset), ScanRight => ScanRight(Stride),
+        ScanLeft => ScanLeft(Stride));
+    impl<S: instructions::Snippet<T>, T> Reflect for instructions::Splice<S, T>
+    where
+        <S as instructions::Snippet<T>>::Chain: Reflect,
+    {
+        fn reflect(bodies: &mut Vec<Vec<Op>>) {
+            <S as instructions::Snippet<T>>::Chain::reflect(bodies);
+        }
+    }
+    impl<T: Reflect> Reflect for instructions::LoopStart<T> {
+        fn reflect(bodies: &mut Vec<Vec<Op>>) {
+            T::reflect(bodies);
+            bodies.push(vec![]);
+        }
+    }
+    impl<T: Reflect> Reflect for instructions::LoopEnd<T> {
+        fn reflect(bodies: &mut Vec<Vec<Op>>) {
+            T::reflect(bodies);
+            let body = bodies.pop().expect("unbalanced loops");
+            push(bodies, Op::Loop(body));
+        }
+    }
+
+    // back to brainfuck source, the optimized commands are spelled out
+    impl fmt::Display for Op {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                Op::Incr => write!(f, "+"),
+                Op::Decr => write!(f, "-"),
+                Op::Right => write!(f, ">"),
+                Op::Left => write!(f, "<"),
+                Op::IncrAt(n) => write!(f, "{}+{}", ">".repeat(*n), "<".repeat(*n)),
+                Op::DecrAt(n) => write!(f, "{}-{}", ">".repeat(*n), "<".repeat(*n)),
+                Op::ScanRight(n) => write!(f, "[{}]", ">".repeat(*n)),
+                Op::ScanLeft(n) => write!(f, "[{}]", "<".repeat(*n)),
+                Op::Print => write!(f, "."),
+                Op::Read => write!(f, ","),
+                Op::Loop(body) => write!(f, "[{}]", source(body)),
+            }
+        }
+    }
+    pub fn source(program: &[Op]) -> String {
+        program.iter().map(Op::to_string).collect()
+    }
+
+    /// run `program` on an empty tape with the semantics of `brainfuck::Interpreter`
+    pub fn run(program: &[Op], input: &[i64]) -> Snapshot {
+        let mut state = Snapshot {
+            pointer: 0,
+            tape: vec![],
+            output: vec![],
+        };
+        let mut input = input.iter().copied();
+        run_on(program, &mut state, &mut input);
+        state
+    }
+    fn run_on(program: &[Op], state: &mut Snapshot, input: &mut impl Iterator<Item = i64>) {
+        fn cell(state: &mut Snapshot, offset: usize) -> &mut i64 {
+            let index = state.pointer + offset;
+            if state.tape.len() <= index {
+                state.tape.resize(index + 1, 0);
+            }
+            &mut state.tape[index]
+        }
+        fn current(state: &Snapshot) -> i64 {
+            state.tape.get(state.pointer).copied().unwrap_or(0)
+        }
+        for op in program {
+            match op {
+                Op::Incr => *cell(state, 0) += 1,
+                Op::Decr => *cell(state, 0) = (current(state) - 1).max(0),
+                Op::Right => state.pointer += 1,
+                Op::Left => state.pointer = state.pointer.saturating_sub(1),
+                Op::IncrAt(n) => *cell(state, *n) += 1,
+                Op::DecrAt(n) => {
+                    let cell = cell(state, *n);
+                    *cell = (*cell - 1).max(0);
+                }
+                Op::ScanRight(n) => {
+                    while current(state) != 0 {
+                        state.pointer += n;
+                    }
+                }
+                Op::ScanLeft(n) => {
+                    while current(state) != 0 {
+                        state.pointer = state.pointer.saturating_sub(*n);
+                    }
+                }
+                Op::Print => state.output.push(current(state)),
+                Op::Read => *cell(state, 0) = input.next().unwrap_or(0),
+                Op::Loop(body) => {
+                    while current(state) != 0 {
+                        run_on(body, state, input);
+                    }
+                }
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::{run, source, Op, Reflect};
+        use crate::brainfuck::{Interpreter, Snapshot};
+        use crate::instructions::*;
+
+        #[test]
+        fn program() {
+            type Code = bf!(Interpreter; +[->[-]<]>.);
+            assert_eq!(
+                Code::program(),
+                [
+                    Op::Incr,
+                    Op::Loop(vec![Op::Decr, Op::Right, Op::Loop(vec![Op::Decr]), Op::Left]),
+                    Op::Right,
+                    Op::Print,
+                ]
+            );
+            type Optimized = bf_opt!(Interpreter; ++[>+<-]>[>]);
+            assert_eq!(
+                Optimized::program(),
+                [
+                    Op::Incr,
+                    Op::Incr,
+                    Op::Loop(vec![Op::IncrAt(1), Op::Decr]),
+                    Op::Right,
+                    Op::ScanRight(1),
+                ]
+            );
+            assert_eq!(source(&Optimized::program()), "++[>+<-]>[>]");
+        }
+        #[test]
+        fn same_as_execute() {
+            macro_rules! same {
+                ($($code:tt)*) => {{
+                    type Code = bf_opt!(Interpreter; $($code)*);
+                    assert_eq!(run(&Code::program(), &[]), Snapshot::of::<Execute<Code>>());
+                }};
+            }
+            same!(++[>+++[>+<-]<-]>>.<<+++[>+++[>>+<<-]<-]>>>.);
+            same!(+++>++<[->-<]>.[>]<<-);
+        }
+    }
+}
+
+pub mod dot {
+    //! control flow of a program as a graphviz graph, render it with `dot -Tsvg`
+
+    use crate::reflect::{source, Op, Reflect};
+
+    /// the control flow graph of `Program` in the dot language
+    pub fn dot<Program: Reflect>() -> String {
+        graph(&Program::program())
+    }
+
+    /// runs of commands are folded into one block, every loop gets a node checking the current
+    /// cell with `enter`, `back` and `exit` edges
+    pub fn graph(program: &[Op]) -> String {
+        let mut graph = Graph::default();
+        let start = graph.node("start", "circle");
+        let last = graph.walk(program, (start, None));
+        let end = graph.node("end", "doublecircle");
+        graph.edge(last, end);
+        let mut out = String::from("digraph program {\n");
+        out += "    node [shape=box, fontname=monospace];\n";
+        for line in graph.lines {
+            out += &format!("    {line};\n");
+        }
+        out + "}\n"
+    }
+
+    /// a node together with the label of the edge leaving it
+    type Exit = (usize, Option<&'static str>);
+
+    #[derive(Default)]
+    struct Graph {
+        lines: Vec<String>,
+        nodes: usize,
+    }
+    impl Graph {
+        fn node(&mut self, label: &str, shape: &str) -> usize {
+            self.lines.push(format!("n{} [label=\"{label}\", shape={shape}]", self.nodes));
+            self.nodes += 1;
+            self.nodes - 1
+        }
+        fn edge(&mut self, (from, label): Exit, to: usize) {
+            self.lines.push(match label {
+                Some(label) => format!("n{from} -> n{to} [label=\"{label}\"]"),
+                None => format!("n{from} -> n{to}"),
+            });
+        }
+        /// add the blocks of `program` coming from `from` and return where it leaves
+        fn walk(&mut self, program: &[Op], mut from: Exit) -> Exit {
+            let mut run = Vec::new();
+            for op in program {
+                let Op::Loop(body) = op else {
+                    run.push(op.clone());
+                    continue;
+                };
+                from = self.flush(&mut run, from);
+                let check = self.node("cell != 0", "diamond");
+                self.edge(from, check);
+                let (last, _) = self.walk(body, (check, Some("enter")));
+                self.edge((last, Some("back")), check);
+                from = (check, Some("exit"));
+            }
+            self.flush(&mut run, from)
+        }
+        fn flush(&mut self, run: &mut Vec<Op>, from: Exit) -> Exit {
+            if run.is_empty() {
+                return from;
+            }
+            let block = self.node(&source(run), "box");
+            self.edge(from, block);
+            run.clear();
+            (block, None)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::{dot, graph};
+        use crate::brainfuck::Interpreter;
+        use crate::reflect::Op;
+
+        #[test]
+        fn blocks_and_loops() {
+            type Code = bf!(Interpreter; ++[>+<-]>.);
+            assert_eq!(
+                dot::<Code>(),
+                "digraph program {
+    node [shape=box, fontname=monospace];
+    n0 [label=\"start\", shape=circle];
+    n1 [label=\"++\", shape=box];
+    n0 -> n1;
+    n2 [label=\"cell != 0\", shape=diamond];
+    n1 -> n2;
+    n3 [label=\">+<-\", shape=box];
+    n2 -> n3 [label=\"enter\"];
+    n3 -> n2 [label=\"back\"];
+    n4 [label=\">.\", shape=box];
+    n2 -> n4 [label=\"exit\"];
+    n5 [label=\"end\", shape=doublecircle];
+    n4 -> n5;
+}
+"
+            );
+        }
+        #[test]
+        fn nested_and_empty() {
+            let nested = graph(&[Op::Loop(vec![Op::Loop(vec![])])]);
+            // the inner loop has no body so it loops back onto itself
+            assert!(nested.contains("n1 -> n2 [label=\"enter\"]"));
+            assert!(nested.contains("n2 -> n2 [label=\"back\"]"));
+            assert!(nested.contains("n2 -> n1 [label=\"back\"]"));
+            assert!(nested.contains("n1 -> n3 [label=\"exit\"]"));
+        }
+    }
+}
+
+pub mod analyze {
+    //! static analysis of brainfuck source, to find out whether a program fits in the recursion
+    //! limit before rustc spends minutes finding out it does not
+    //!
+    //! values and the pointer are followed as far as they are known without running any loop,
+    //! the semantics are those of `brainfuck::Interpreter`
+
+    use crate::reflect::Op;
+    use std::{collections::HashMap, fmt};
+
+    /// iterations assumed for a loop whose counter is not known
+    const UNKNOWN_ITERATIONS: i64 = 256;
+    /// levels used by `Execute` on top of the program itself
+    const BASE_DEPTH: usize = 4;
+    /// levels used by a loop on top of its body and its rounds
+    const LOOP_DEPTH: usize = 5;
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct Analysis {
+        pub commands: usize,
+        /// the deepest loop nesting, 0 without loops
+        pub nesting: usize,
+        /// every loop in source order
+        pub loops: Vec<LoopInfo>,
+        /// the number of cells the program can touch, `None` if the pointer can move arbitrarily
+        /// far to the right
+        pub extent: Option<usize>,
+        /// an estimate of the type depth `Execute` needs
+        pub depth: usize,
+    }
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct LoopInfo {
+        /// 1 for a top level loop
+        pub nesting: usize,
+        /// how far the pointer moves in one iteration, `None` if it depends on the tape
+        pub balance: Option<i64>,
+        /// the number of iterations when it is known
+        pub iterations: Option<i64>,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct Unbalanced {
+        /// index of the unmatched bracket in the source
+        pub position: usize,
+    }
+
+    /// parse `source`, any character that is not a command is ignored
+    pub fn parse(source: &str) -> Result<Vec<Op>, Unbalanced> {
+        let mut bodies = vec![vec![]];
+        let mut opened = vec![];
+        for (position, c) in source.char_indices() {
+            let op = match c {
+                '+' => Op::Incr,
+                '-' => Op::Decr,
+                '>' => Op::Right,
+                '<' => Op::Left,
+                '.' => Op::Print,
+                ',' => Op::Read,
+                '[' => {
+                    opened.push(position);
+                    bodies.push(vec![]);
+                    continue;
+                }
+                ']' => {
+                    opened.pop().ok_or(Unbalanced { position })?;
+                    Op::Loop(bodies.pop().unwrap())
+                }
+                _ => continue,
+            };
+            bodies.last_mut().unwrap().push(op);
+        }
+        match opened.pop() {
+            Some(position) => Err(Unbalanced { position }),
+            None => Ok(bodies.pop().unwrap()),
+        }
+    }
+
+    pub fn analyze(source: &str) -> Result<Analysis, Unbalanced> {
+        Ok(analyze_program(&parse(source)?))
+    }
+
+    /// analyze an already parsed program, like one from `reflect::Reflect::program`
+    pub fn analyze_program(program: &[Op]) -> Analysis {
+        let mut walker = Walker {
+            state: State {
+                pointer: Some(0),
+                cells: HashMap::new(),
+                clobbered: false,
+            },
+            top: 0,
+            unbounded: false,
+            max_value: 0,
+            loops: vec![],
+            nesting: 0,
+        };
+        let cost = walker.walk(program, 0);
+        Analysis {
+            commands: count(program),
+            nesting: walker.nesting,
+            loops: walker.loops,
+            extent: (!walker.unbounded).then_some(walker.top + 1),
+            // reading a value walks all of it, so large values need depth as well
+            depth: cost.max(walker.max_value as usize) + BASE_DEPTH,
+        }
+    }
+
+    impl Analysis {
+        /// a `#![recursion_limit]` leaving some room above `depth`
+        pub fn recursion_limit(&self) -> usize {
+            let padded = self.depth + self.depth / 4;
+            padded.div_ceil(64).max(2) * 64
+        }
+    }
+    impl fmt::Display for Analysis {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            writeln!(f, "commands: {}", self.commands)?;
+            writeln!(f, "loop nesting: {}", self.nesting)?;
+            for (i, info) in self.loops.iter().enumerate() {
+                write!(f, "loop {i} at nesting {}: ", info.nesting)?;
+                match info.balance {
+                    Some(balance) => write!(f, "moves {balance} per iteration")?,
+                    None => write!(f, "moves depending on the tape")?,
+                }
+                match info.iterations {
+                    Some(iterations) => writeln!(f, ", runs {iterations} times")?,
+                    None => writeln!(f)?,
+                }
+            }
+            match self.extent {
+                Some(extent) => writeln!(f, "tape extent: {extent} cells")?,
+                None => writeln!(f, "tape extent: unbounded")?,
+            }
+            writeln!(f, "estimated depth: {}", self.depth)?;
+            write!(f, "#![recursion_limit = \"{}\"]", self.recursion_limit())
+        }
+    }
+
+    fn count(program: &[Op]) -> usize {
+        program
+            .iter()
+            .map(|op| match op {
+                Op::Loop(body) => 2 + count(body),
+                _ => 1,
+            })
+            .sum()
+    }
+
+    /// how far one run of `program` moves the pointer
+    fn balance(program: &[Op]) -> Option<i64> {
+        program.iter().try_fold(0, |sum, op| match op {
+            Op::Right => Some(sum + 1),
+            Op::Left => Some(sum - 1),
+            Op::ScanRight(_) | Op::ScanLeft(_) => None,
+            Op::Loop(body) => (balance(body)? == 0).then_some(sum),
+            _ => Some(sum),
+        })
+    }
+
+    /// the cells relative to the pointer `program` may write, `None` if that depends on the tape
+    fn writes(program: &[Op], mut offset: i64, written: &mut Vec<i64>) -> Option<i64> {
+        for op in program {
+            match op {
+                Op::Right => offset += 1,
+                Op::Left => offset -= 1,
+                Op::Incr | Op::Decr | Op::Read => written.push(offset),
+                Op::IncrAt(n) | Op::DecrAt(n) => written.push(offset + *n as i64),
+                Op::ScanRight(_) | Op::ScanLeft(_) => return None,
+                Op::Loop(body) => {
+                    if writes(body, offset, written)? != offset {
+                        return None;
+                    }
+                }
+                Op::Print => {}
+            }
+        }
+        Some(offset)
+    }
+
+    /// the change one run of `program` makes to the cell under the pointer when it is balanced
+    fn counter_step(program: &[Op]) -> Option<i64> {
+        let mut offset = 0;
+        let mut step = 0;
+        for op in program {
+            match op {
+                Op::Right => offset += 1,
+                Op::Left => offset -= 1,
+                Op::Incr if offset == 0 => step += 1,
+                Op::Decr if offset == 0 => step -= 1,
+                Op::Read if offset == 0 => return None,
+                Op::IncrAt(n) | Op::DecrAt(n) if offset + *n as i64 == 0 => return None,
+                Op::Loop(_) if offset == 0 => return None,
+                // a nested loop writing the counter changes it by an unknown amount
+                Op::Loop(body) => {
+                    let mut written = vec![];
+                    if writes(body, offset, &mut written)? != offset || written.contains(&0) {
+                        return None;
+                    }
+                }
+                Op::ScanRight(_) | Op::ScanLeft(_) => return None,
+                _ => {}
+            }
+        }
+        (offset == 0).then_some(step)
+    }
+
+    #[derive(Clone)]
+    struct State {
+        /// `None` once it depends on the tape
+        pointer: Option<usize>,
+        /// cells missing here are still zero, unless `clobbered`, cells mapped to `None` are
+        /// not known
+        cells: HashMap<usize, Option<i64>>,
+        clobbered: bool,
+    }
+    impl State {
+        fn get(&self, cell: usize) -> Option<i64> {
+            match self.cells.get(&cell) {
+                Some(&value) => value,
+                None if self.clobbered => None,
+                None => Some(0),
+            }
+        }
+        fn forget_all(&mut self) {
+            self.pointer = None;
+            self.cells.clear();
+            self.clobbered = true;
+        }
+    }
+
+    struct Walker {
+        state: State,
+        /// the rightmost cell the pointer reached
+        top: usize,
+        unbounded: bool,
+        max_value: i64,
+        loops: Vec<LoopInfo>,
+        nesting: usize,
+    }
+    impl Walker {
+        /// follow `program` and return the depth it needs
+        fn walk(&mut self, program: &[Op], nesting: usize) -> usize {
+            self.nesting = self.nesting.max(nesting);
+            let mut cost = 0;
+            // every command nests the ones before it
+            for (i, op) in program.iter().enumerate() {
+                let after = program.len() - i - 1;
+                cost = cost.max(after + self.step(op, nesting));
+            }
+            cost
+        }
+        fn reach(&mut self, cell: usize) {
+            self.top = self.top.max(cell);
+        }
+        fn update(&mut self, offset: usize, f: impl Fn(Option<i64>) -> Option<i64>) {
+            let Some(pointer) = self.state.pointer else {
+                self.state.clobbered = true;
+                self.state.cells.clear();
+                return;
+            };
+            self.reach(pointer + offset);
+            let value = f(self.state.get(pointer + offset));
+            self.max_value = self.max_value.max(value.unwrap_or(0));
+            self.state.cells.insert(pointer + offset, value);
+        }
+        fn step(&mut self, op: &Op, nesting: usize) -> usize {
+            match op {
+                Op::Incr => self.update(0, |v| v.map(|v| v + 1)),
+                Op::Decr => self.update(0, |v| v.map(|v| (v - 1).max(0))),
+                Op::IncrAt(n) => self.update(*n, |v| v.map(|v| v + 1)),
+                Op::DecrAt(n) => self.update(*n, |v| v.map(|v| (v - 1).max(0))),
+                Op::Read => self.update(0, |_| None),
+                Op::Right => {
+                    self.state.pointer = self.state.pointer.map(|p| p + 1);
+                    if let Some(p) = self.state.pointer {
+                        self.reach(p);
+                    }
+                }
+                Op::Left => self.state.pointer = self.state.pointer.map(|p| p.saturating_sub(1)),
+                Op::ScanRight(_) | Op::ScanLeft(_) => {
+                    self.unbounded = true;
+                    self.state.pointer = None;
+                }
+                Op::Print => {}
+                Op::Loop(body) => return self.run_loop(body, nesting + 1),
+            }
+            1
+        }
+        fn run_loop(&mut self, body: &[Op], nesting: usize) -> usize {
+            let entry = self.state.pointer.and_then(|p| self.state.get(p));
+            let balance = balance(body);
+            let iterations = match (entry, counter_step(body)) {
+                (Some(0), _) => Some(0),
+                (Some(value), Some(-1)) => Some(value),
+                _ => None,
+            };
+            self.loops.push(LoopInfo {
+                nesting,
+                balance,
+                iterations,
+            });
+            if iterations == Some(0) {
+                return 1;
+            }
+            // whatever the body writes is unknown from the second iteration on
+            let mut written = vec![];
+            match (self.state.pointer, balance, writes(body, 0, &mut written)) {
+                (Some(pointer), Some(0), Some(_)) => {
+                    for offset in written {
+                        if let Ok(cell) = usize::try_from(pointer as i64 + offset) {
+                            self.state.cells.insert(cell, None);
+                        }
+                    }
+                }
+                _ => self.state.forget_all(),
+            }
+            let start = self.state.pointer;
+            let cost = self.walk(body, nesting);
+            match (start, balance) {
+                (Some(pointer), Some(0)) => {
+                    self.state.pointer = Some(pointer);
+                    self.state.cells.insert(pointer, Some(0));
+                }
+                _ => {
+                    self.unbounded |= balance != Some(0);
+                    self.state.forget_all();
+                }
+            }
+            // every doubling of the iterations adds a round, the first few fit in the levels the
+            // loop needs anyway
+            let doublings = iterations.unwrap_or(UNKNOWN_ITERATIONS).max(1).ilog2() as usize;
+            cost + (2 * doublings).saturating_sub(3) + LOOP_DEPTH
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::{analyze, analyze_program, LoopInfo, Unbalanced};
+        use crate::brainfuck::Interpreter;
+        use crate::reflect::Reflect;
+
+        #[test]
+        fn straight_line() {
+            let analysis = analyze("+++>>-<.").unwrap();
+            assert_eq!(analysis.commands, 8);
+            assert_eq!(analysis.nesting, 0);
+            assert_eq!(analysis.extent, Some(3));
+            assert_eq!(analysis.depth, 8 + 4);
+            assert_eq!(analysis.recursion_limit(), 128);
+        }
+        #[test]
+        fn loops() {
+            let analysis = analyze("++++[>++[>+<-]<-]>>[>]").unwrap();
+            assert_eq!(analysis.nesting, 2);
+            assert_eq!(
+                analysis.loops,
+                [
+                    LoopInfo {
+                        nesting: 1,
+                        balance: Some(0),
+                        iterations: Some(4),
+                    },
+                    // the inner counter is rewritten every iteration so it is not known
+                    LoopInfo {
+                        nesting: 2,
+                        balance: Some(0),
+                        iterations: None,
+                    },
+                    LoopInfo {
+                        nesting: 1,
+                        balance: Some(1),
+                        iterations: None,
+                    },
+                ]
+            );
+            assert_eq!(analysis.extent, None);
+            assert_eq!(analyze("++[>+<-]>>").unwrap().extent, Some(3));
+            // a loop that is never entered costs nothing
+            assert_eq!(analyze("[>>>+<<<-]").unwrap().loops[0].iterations, Some(0));
+            // the inner loop adds cell 1 back to the counter
+            let refilled = analyze("+++>++<[>[<+>-]<-]").unwrap();
+            assert_eq!(refilled.loops[0].iterations, None);
+        }
+        #[test]
+        fn depth() {
+            // rustc needed a limit of 210, 213 and 221 for these, and 604 for the one below
+            let body = "+-".repeat(100);
+            assert_eq!(analyze(&format!("+[{body}-]")).unwrap().depth, 210);
+            assert_eq!(analyze(&format!("++++++++[{body}-]")).unwrap().depth, 213);
+            let many = "+".repeat(128);
+            assert_eq!(analyze(&format!("{many}[{body}-]")).unwrap().depth, 221);
+            let long = analyze(&"+-".repeat(300)).unwrap();
+            assert_eq!(long.depth, 604);
+            assert_eq!(long.recursion_limit(), 768);
+        }
+        #[test]
+        fn unbalanced() {
+            assert_eq!(analyze("+[[-]").unwrap_err(), Unbalanced { position: 1 });
+            assert_eq!(analyze("+]").unwrap_err(), Unbalanced { position: 1 });
+        }
+        #[test]
+        fn reflected() {
+            type Code = bf_opt!(Interpreter; ++[>+<-]>.);
+            assert_eq!(analyze_program(&Code::program()).extent, Some(2));
+        }
+    }
+}
+
+pub mod brainfuck {
+    use crate::{
+        config::{self, Settings},
+        indexed, instructions, linked_list,
+        linked_list::InvalidCharError,
+        numbers::{self, Zero},
+        operators,
+    };
+    use std::fmt;
+
+    /// `Config` picks the semantics, see `config::Config`, and `Input` is what `,` reads from
+    pub struct InterpreterBase<Ram, Output, Config = config::Default, Input = linked_list::End>(
+        Ram,
+        Output,
+        Config,
+        Input,
+    );
+    impl<R1, O1, I1, R2, O2, I2, C, Fuel> numbers::Matches<InterpreterBase<R2, O2, C, I2>, Fuel>
+        for InterpreterBase<R1, O1, C, I1>
+    where
+        (R1, (O1, I1)): numbers::Matches<(R2, (O2, I2)), Fuel>,
+    {
+        type Output = <(R1, (O1, I1)) as numbers::Matches<(R2, (O2, I2)), Fuel>>::Output;
+    }
+
+    pub trait Debug {
+        type Output;
+        type Ram;
+    }
+    // the output is stored newest first so that printing does not have to walk it, it is only
+    // put back in order when read
+    impl<Ram, Output, C, Input> Debug for InterpreterBase<Ram, Output, C, Input>
+    where
+        Ram: config::Holds<C>,
+        Output: indexed::Reverse,
+    {
+        type Output = <Output as indexed::Reverse>::Indexed;
+        type Ram = Ram;
+    }
+    macro_rules! debug_wrapper {
+        ($($items:ident),*) => {
+                $(
+                    impl<T: Debug> Debug for instructions::$items<T>{
+                        type Output = <T as Debug>::Output;
+                        type Ram = <T as Debug>::Ram;
+                    }
+                )*
+        };
+    }
+    debug_wrapper!(Incr, Decr, MoveRight, MoveLeft, LoopStart, LoopEnd, Print, Read);
+    impl<Offset, T: Debug> Debug for instructions::IncrAt<Offset, T> {
+        type Output = <T as Debug>::Output;
+        type Ram = <T as Debug>::Ram;
+    }
+    impl<Offset, T: Debug> Debug for instructions::DecrAt<Offset, T> {
+        type Output = <T as Debug>::Output;
+        type Ram = <T as Debug>::Ram;
+    }
+    impl<Stride, T: Debug> Debug for instructions::ScanRight<Stride, T> {
+        type Output = <T as Debug>::Output;
+        type Ram = <T as Debug>::Ram;
+    }
+    impl<Stride, T: Debug> Debug for instructions::ScanLeft<Stride, T> {
+        type Output = <T as Debug>::Output;
+        type Ram = <T as Debug>::Ram;
+    }
+    impl<S, T> Debug for instructions::Splice<S, T>
+    where
+        S: instructions::Snippet<T>,
+        <S as instructions::Snippet<T>>::Chain: Debug,
+    {
+        type Output = <<S as instructions::Snippet<T>>::Chain as Debug>::Output;
+        type Ram = <<S as instructions::Snippet<T>>::Chain as Debug>::Ram;
+    }
+
+    impl<Op, Ram, Output, C, Input> indexed::VecOp<Op> for InterpreterBase<Ram, Output, C, Input>
+    where
+        Ram: indexed::VecOp<Op>,
+    {
+        type Indexed = InterpreterBase<<Ram as indexed::VecOp<Op>>::Indexed, Output, C, Input>;
+    }
+    impl<Op, Ram, Output, C, Input> indexed::IndexOp<Op> for InterpreterBase<Ram, Output, C, Input>
+    where
+        Ram: indexed::IndexOp<Op>,
+    {
+        type Indexed = InterpreterBase<<Ram as indexed::IndexOp<Op>>::Indexed, Output, C, Input>;
+    }
+    impl<Op, Offset, Ram, Output, C, Input> indexed::VecOpAt<Op, Offset>
+        for InterpreterBase<Ram, Output, C, Input>
+    where
+        Ram: indexed::VecOpAt<Op, Offset>,
+    {
+        type Indexed =
+            InterpreterBase<<Ram as indexed::VecOpAt<Op, Offset>>::Indexed, Output, C, Input>;
+    }
+
+    pub trait Print {
+        type Out;
+    }
+    impl<Ram, Output, C, Input> Print for InterpreterBase<Ram, Output, C, Input>
+    where
+        Ram: indexed::Get,
+        Output: indexed::Prepend<<Ram as indexed::Get>::Val>,
+    {
+        type Out = InterpreterBase<
+            Ram,
+            <Output as indexed::Prepend<<Ram as indexed::Get>::Val>>::Indexed,
+            C,
+            Input,
+        >;
+    }
+
+    pub trait Read {
+        type Out;
+    }
+    type Cells<C> = <C as Settings>::Cells;
+    type Cell<C, Val> = <Cells<C> as config::CellFrom<Val>>::Cell;
+    impl<Ram, Output, C, Val, Rest> Read
+        for InterpreterBase<Ram, Output, C, linked_list::Node<Val, Rest>>
+    where
+        C: Settings,
+        Cells<C>: config::CellFrom<Val>,
+        Ram: indexed::VecOp<operators::Set<Cell<C, Val>>>,
+    {
+        type Out = InterpreterBase<
+            <Ram as indexed::VecOp<operators::Set<Cell<C, Val>>>>::Indexed,
+            Output,
+            C,
+            Rest,
+        >;
+    }
+    // out of input, the eof policy decides
+    impl<Ram, Output, C, Blank> Read for InterpreterBase<Ram, Output, C, linked_list::End<Blank>>
+    where
+        C: Settings,
+        <C as Settings>::Eof: config::AtEof<Cells<C>, Ram>,
+    {
+        type Out = InterpreterBase<
+            <<C as Settings>::Eof as config::AtEof<Cells<C>, Ram>>::Ram,
+            Output,
+            C,
+            linked_list::End<Blank>,
+        >;
+    }
+
+    /// the output rendered with the encoding of the config
+    pub trait Render {
+        fn render() -> Result<String, InvalidCharError>;
+    }
+    impl<Ram, Output, C, Input> Render for InterpreterBase<Ram, Output, C, Input>
+    where
+        Self: Debug,
+        GetOutput<Self>: indexed::Debug,
+        C: Settings,
+        <C as Settings>::Encoding: config::Encoding,
+    {
+        fn render() -> Result<String, InvalidCharError> {
+            <<C as Settings>::Encoding as config::Encoding>::render(
+                <GetOutput<Self> as indexed::Debug>::data(),
+            )
+        }
+    }
+    pub type GetOutput<T> = <T as Debug>::Output;
+
+    /// the pointer, tape and output of a state at runtime
+    #[derive(Clone, PartialEq, Eq)]
+    pub struct Snapshot {
+        pub pointer: usize,
+        pub tape: Vec<i64>,
+        pub output: Vec<i64>,
+    }
+    impl Snapshot {
+        pub fn of<State>() -> Self
+        where
+            State: Debug,
+            <State as Debug>::Ram: indexed::Debug,
+            GetOutput<State>: indexed::Debug,
+        {
+            Snapshot {
+                pointer: <<State as Debug>::Ram as indexed::Debug>::index(),
+                tape: <<State as Debug>::Ram as indexed::Debug>::data(),
+                output: <GetOutput<State> as indexed::Debug>::data(),
+            }
+        }
+        /// the cell at `index`, cells past the end of the tape have never been touched
+        fn cell(&self, index: usize) -> i64 {
+            self.tape.get(index).copied().unwrap_or(0)
+        }
+        /// what changed going from `self` to `after`, one change per line
+        pub fn diff(&self, after: &Snapshot) -> String {
+            let mut changes = Vec::new();
+            if self.pointer != after.pointer {
+                changes.push(format!("pointer: {} -> {}", self.pointer, after.pointer));
+            }
+            for i in 0..self.tape.len().max(after.tape.len()) {
+                if self.cell(i) != after.cell(i) {
+                    changes.push(format!("cell {i}: {} -> {}", self.cell(i), after.cell(i)));
+                }
+            }
+            match after.output.strip_prefix(self.output.as_slice()) {
+                Some([]) => {}
+                Some(printed) => changes.push(format!("printed: {printed:?}")),
+                None => changes.push(format!("output: {:?} -> {:?}", self.output, after.output)),
+            }
+            changes.join("\n")
+        }
+    }
+    // the tape with the current cell in brackets, `0 [3] 1 | output: [3]`
+    impl fmt::Display for Snapshot {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            for i in 0..self.tape.len().max(self.pointer + 1) {
+                if i > 0 {
+                    write!(f, " ")?;
+                }
+                if i == self.pointer {
+                    write!(f, "[{}]", self.cell(i))?;
+                } else {
+                    write!(f, "{}", self.cell(i))?;
+                }
+            }
+            write!(f, " | output: {:?}", self.output)
+        }
+    }
+    // so failing assertions show the pointer too
+    impl fmt::Debug for Snapshot {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            fmt::Display::fmt(self, f)
+        }
+    }
+
+    /// an empty interpreter with the semantics of `C` reading from `Input`
+    pub type Interpreter<C = config::Default, Input = linked_list::End> = InterpreterBase<
+        indexed::Indexed<
+            Zero,
+            linked_list::End<<Cells<C> as config::Cells>::Blank>,
+            <C as Settings>::Tape,
+        >,
+        list!(),
+        C,
+        Input,
+    >;
+    /// cells are signed, decrementing zero goes negative rather than staying at zero
+    pub type SignedInterpreter = Interpreter<config::Config<config::Signed, indexed::Unbounded>>;
+
+    #[cfg(test)]
+    mod tests {
+        use super::{Interpreter, Snapshot};
+        use crate::instructions::Execute;
+
+        #[test]
+        fn snapshot() {
+            type Before = Execute<bf!(Interpreter; ++>+++<.)>;
+            let before = Snapshot::of::<Before>();
+            assert_eq!(
+                before,
+                Snapshot {
+                    pointer: 0,
+                    tape: vec![2, 3],
+                    output: vec![2],
+                }
+            );
+            assert_eq!(before.to_string(), "[2] 3 | output: [2]");
+
+            type After = Execute<bf!(Before; [>+<-]>>.)>;
+            let after = Snapshot::of::<After>();
+            // the pointer is past the end of the tape, the cell under it is still shown
+            assert_eq!(after.to_string(), "0 5 [0] | output: [2, 0]");
+            assert_eq!(
+                before.diff(&after),
+                "pointer: 0 -> 2\ncell 0: 2 -> 0\ncell 1: 3 -> 5\nprinted: [0]"
+            );
+            assert_eq!(after.diff(&after), "");
+        }
+    }
+}
+
+/// the semantics of an interpreter, bundled so they can be passed around as one type parameter
+pub mod config {
+    use crate::{
+        indexed::{self, Unbounded},
+        linked_list::{self, InvalidCharError},
+        numbers::{Byte, ByteMax, Minus, Pos, Zero},
+        operators,
+    };
+
+    /// `Cells` is what a cell holds, `Tape` is one of the tapes in `indexed`, `Eof` what `,`
+    /// does once the input is used up and `Encoding` how the output is rendered
+    pub struct Config<Cells, Tape, Eof = EofZero, Encoding = Raw>(Cells, Tape, Eof, Encoding);
+    pub type Default = Config<Saturating, Unbounded>;
+
+    /// unbounded cells, decrementing zero stays at zero
+    pub struct Saturating;
+    /// unbounded cells that can go negative
+    pub struct Signed;
+    /// cells are bytes, wrapping around at 0 and 255
+    pub struct Wrap8;
+    /// reading past the end of the input sets the cell to zero
+    pub struct EofZero;
+    /// reading past the end of the input leaves the cell alone
+    pub struct EofUnchanged;
+    /// the output is rendered as numbers separated by spaces
+    pub struct Raw;
+    /// the output is rendered as utf-8 bytes
+    pub struct Utf8;
+
+    pub trait Settings {
+        type Cells;
+        type Tape;
+        type Eof;
+        type Encoding;
+    }
+    impl<Cells, Tape, Eof, Encoding> Settings for Config<Cells, Tape, Eof, Encoding> {
+        type Cells = Cells;
+        type Tape = Tape;
+        type Eof = Eof;
+        type Encoding = Encoding;
+    }
+
+    /// implemented by the tapes that are of the kind `C` asks for and hold its cells, a state
+    /// can only be read back when its tape matches its config
+    ///
+    /// ```compile_fail,E0277
+    /// use brainfuck::{
+    ///     brainfuck::{GetOutput, InterpreterBase},
+    ///     config::{Config, Saturating},
+    ///     indexed::{Circular, Debug, EmptyIndexed},
+    ///     numbers::Three,
+    /// };
+    /// // an unbounded tape under a config asking for a circular one
+    /// type Ring = Config<Saturating, Circular<Three>>;
+    /// type State = InterpreterBase<EmptyIndexed, EmptyIndexed, Ring>;
+    /// let _ = GetOutput::<State>::data();
+    /// ```
+    #[diagnostic::on_unimplemented(
+        message = "the tape `{Self}` does not match the config `{C}`",
+        label = "start from `brainfuck::Interpreter<C>` to get the tape the config asks for"
+    )]
+    pub trait Holds<C> {}
+    impl<Cells, Tape, Eof, Encoding, Idx, Data> Holds<Config<Cells, Tape, Eof, Encoding>>
+        for indexed::Indexed<Idx, Data, Tape>
+    where
+        Cells: self::Cells,
+        Data: linked_list::Padded<<Cells as self::Cells>::Blank>,
+    {
+    }
+
+    /// the value of a fresh cell
+    pub trait Cells {
+        type Blank;
+    }
+    impl Cells for Saturating {
+        type Blank = Zero;
+    }
+    impl Cells for Signed {
+        type Blank = Pos<Zero>;
+    }
+    impl Cells for Wrap8 {
+        type Blank = Byte<Zero, ByteMax>;
+    }
+
+    /// the cell holding the number `N`
+    pub trait CellFrom<N> {
+        type Cell;
+    }
+    impl<N> CellFrom<N> for Saturating {
+        type Cell = N;
+    }
+    impl<N> CellFrom<N> for Signed {
+        type Cell = Pos<N>;
+    }
+    // `N` is expected to fit in a byte
+    impl<N> CellFrom<N> for Wrap8
+    where
+        ByteMax: Minus<N>,
+    {
+        type Cell = Byte<N, <ByteMax as Minus<N>>::Output>;
+    }
+
+    /// what `Ram` turns into when `,` reads past the end of the input
+    pub trait AtEof<Cells, Ram> {
+        type Ram;
+    }
+    impl<Cells: self::Cells, Ram> AtEof<Cells, Ram> for EofZero
+    where
+        Ram: indexed::VecOp<operators::Set<<Cells as self::Cells>::Blank>>,
+    {
+        type Ram = <Ram as indexed::VecOp<operators::Set<<Cells as self::Cells>::Blank>>>::Indexed;
+    }
+    impl<Cells, Ram> AtEof<Cells, Ram> for EofUnchanged {
+        type Ram = Ram;
+    }
+
+    pub trait Encoding {
+        fn render(output: Vec<i64>) -> Result<String, InvalidCharError>;
+    }
+    impl Encoding for Raw {
+        fn render(output: Vec<i64>) -> Result<String, InvalidCharError> {
+            let values: Vec<_> = output.iter().map(i64::to_string).collect();
+            Ok(values.join(" "))
+        }
+    }
+    impl Encoding for Utf8 {
+        fn render(output: Vec<i64>) -> Result<String, InvalidCharError> {
+            let data = output
+                .into_iter()
+                .map(|x| u8::try_from(x).map_err(|_| InvalidCharError))
+                .collect::<Result<Vec<_>, _>>()?;
+            String::from_utf8(data).map_err(|_| InvalidCharError)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::{Config, EofUnchanged, EofZero, Saturating, Utf8, Wrap8};
+        use crate::{
+            brainfuck::{self, GetOutput, Render},
+            indexed::{Circular, Debug, Strict, Unbounded},
+            instructions::Execute,
+            numbers::{Nine, Three, Two},
+        };
+        type GetRam<T> = <T as brainfuck::Debug>::Ram;
+
+        #[test]
+        fn signed_cells() {
+            type Ram = brainfuck::SignedInterpreter;
+            // decrementing zero goes negative and loops run until the cell is exactly zero
+            type Code = bf!(Ram; --.[>+<+]>.<<-.);
+            assert_eq!(GetOutput::<Execute<Code>>::data(), [-2, 2, -1]);
+            assert_eq!(GetRam::<Execute<Code>>::data(), [-1, 2]);
+
+            type Scan = bf!(Ram; ->->-<<[>]>+);
+            assert_eq!(GetRam::<Execute<Scan>>::data(), [-1, -1, -1, 0, 1]);
+            assert_eq!(GetRam::<Execute<Scan>>::index(), 4);
+        }
+        #[test]
+        fn circular_tape() {
+            type Ram = brainfuck::Interpreter<Config<Saturating, Circular<Three>>>;
+            // moving right off the last cell wraps to the first, and left of the first to the last
+            type Code = bf!(Ram; +>++>+++>+.<.);
+            assert_eq!(GetOutput::<Execute<Code>>::data(), [2, 3]);
+            assert_eq!(GetRam::<Execute<Code>>::data(), [2, 2, 3]);
+            assert_eq!(GetRam::<Execute<Code>>::index(), 2);
+
+            type Scan = bf!(Ram; >+>+[>]);
+            assert_eq!(GetRam::<Execute<Scan>>::index(), 0);
+            type Offset = bf_opt!(brainfuck::Interpreter<Config<Saturating, Circular<Two>>>; >>+<<);
+            assert_eq!(GetRam::<Execute<Offset>>::data(), [1]);
+        }
+        #[test]
+        fn strict_tape() {
+            type Ram = brainfuck::Interpreter<Config<Saturating, Strict<Three>>>;
+            // moving past either end is a compile error, see `indexed::Strict`
+            type Code = bf!(Ram; >>+<+<+.);
+            assert_eq!(GetOutput::<Execute<Code>>::data(), [1]);
+            assert_eq!(GetRam::<Execute<Code>>::data(), [1, 1, 1]);
+            // `bf_opt!` drops the `><` before the tape is ever checked, `bf!` would not compile
+            type Dropped = bf_opt!(Ram; >>><+);
+            assert_eq!(GetRam::<Execute<Dropped>>::data(), [0, 0, 1]);
+        }
+        #[test]
+        fn wrapping_cells() {
+            type Ram = brainfuck::Interpreter<Config<Wrap8, Unbounded>>;
+            type Code = bf!(Ram; -.+.);
+            assert_eq!(GetOutput::<Execute<Code>>::data(), [255, 0]);
+
+            // the whole semantics in one alias
+            type Tiny = brainfuck::Interpreter<Config<Wrap8, Circular<Three>, EofZero>>;
+            type Wrapped = bf!(Tiny; >>>-.<.);
+            assert_eq!(GetOutput::<Execute<Wrapped>>::data(), [255, 0]);
+            assert_eq!(GetRam::<Execute<Wrapped>>::index(), 2);
+        }
+        #[test]
+        fn input() {
+            type Input = ll!(Two, Three);
+            type Zeroed = brainfuck::Interpreter<Config<Saturating, Unbounded, EofZero>, Input>;
+            type Code = bf!(Zeroed; ,.,.,.);
+            assert_eq!(GetOutput::<Execute<Code>>::data(), [2, 3, 0]);
+
+            type Kept = brainfuck::Interpreter<Config<Saturating, Unbounded, EofUnchanged>, Input>;
+            type Same = bf!(Kept; ,.,.,.);
+            assert_eq!(GetOutput::<Execute<Same>>::data(), [2, 3, 3]);
+
+            type Bytes = brainfuck::Interpreter<Config<Wrap8, Unbounded>, ll!(Nine)>;
+            type Wrapped = bf_opt!(Bytes; ,----------.);
+            assert_eq!(GetOutput::<Execute<Wrapped>>::data(), [255]);
+
+            // reads count down a loop like any other value
+            type Counted = bf!(Zeroed; ,[>+++<-]>.);
+            assert_eq!(GetOutput::<Execute<Counted>>::data(), [6]);
+        }
+        #[test]
+        fn render() {
+            type Hi =
+                bf_opt!(brainfuck::Interpreter; ++++++++[>+++++++++<-]>.<+++[>+++++++++++<-]>.);
+            assert_eq!(<Execute<Hi>>::render().unwrap(), "72 105");
+            type Text = brainfuck::Interpreter<Config<Saturating, Unbounded, EofZero, Utf8>>;
+            type Rendered =
+                bf_opt!(Text; ++++++++[>+++++++++<-]>.<+++[>+++++++++++<-]>.);
+            assert_eq!(<Execute<Rendered>>::render().unwrap(), "Hi");
+        }
+    }
+}
+
+pub mod const_eval {
+    //! brainfuck interpreter running in const evaluation, an alternative to the type level
+    //! interpreter that is not bound by the recursion limit
+    //!
+    //! semantics follow the type level interpreter: cells are unbounded and decrementing 0
+    //! stays 0, moving left of cell 0 stays at cell 0, `,` reads a byte or 0 on eof
+
+    pub const TAPE_LEN: usize = 30000;
+
+    /// the output of a program, holds at most `N` values
+    pub struct Output<const N: usize> {
+        data: [i64; N],
+        len: usize,
+    }
+    impl<const N: usize> Output<N> {
+        const fn push(&mut self, val: i64) {
+            assert!(self.len < N, "output buffer is full");
+            self.data[self.len] = val;
+            self.len += 1;
+        }
+        pub const fn as_slice(&self) -> &[i64] {
+            self.data.split_at(self.len).0
+        }
+        pub fn string(&self) -> Result<String, crate::linked_list::InvalidCharError> {
+            let data = self
+                .as_slice()
+                .iter()
+                .map(|&x| u8::try_from(x).map_err(|_| crate::linked_list::InvalidCharError))
+                .collect::<Result<Vec<_>, _>>()?;
+            String::from_utf8(data).map_err(|_| crate::linked_list::InvalidCharError)
+        }
+    }
+
+    /// find the bracket matching the one at `pc`
+    const fn matching(program: &[u8], mut pc: usize) -> usize {
+        let forward = program[pc] == b'[';
+        let mut depth = 0usize;
+        loop {
+            match program[pc] {
+                b'[' if forward => depth += 1,
+                b']' if !forward => depth += 1,
+                b'[' | b']' => depth -= 1,
+                _ => {}
+            }
+            if depth == 0 {
+                return pc;
+            }
+            pc = if forward { pc + 1 } else { pc - 1 };
+        }
+    }
+
+    /// run `program` on an empty tape, any byte that is not a command is ignored
+    pub const fn run<const N: usize>(program: &[u8], input: &[u8]) -> Output<N> {
+        let mut tape = [0i64; TAPE_LEN];
+        let mut ptr = 0usize;
+        let mut read = 0;
+        let mut pc = 0;
+        let mut output = Output {
+            data: [0; N],
+            len: 0,
+        };
+        while pc < program.len() {
+            match program[pc] {
+                b'>' => ptr += 1,
+                b'<' => ptr = ptr.saturating_sub(1),
+                b'+' => tape[ptr] += 1,
+                b'-' if tape[ptr] > 0 => tape[ptr] -= 1,
+                b'.' => output.push(tape[ptr]),
+                b',' => {
+                    tape[ptr] = if read < input.len() {
+                        read += 1;
+                        input[read - 1] as i64
+                    } else {
+                        0
+                    };
+                }
+                b'[' if tape[ptr] == 0 => pc = matching(program, pc),
+                b']' if tape[ptr] != 0 => pc = matching(program, pc),
+                _ => {}
+            }
+            pc += 1;
+        }
+        output
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::{run, Output};
+        use crate::{
+            brainfuck::{GetOutput, Interpreter},
+            indexed::Debug,
+            instructions::*,
+        };
+
+        // every program is run through both the type level and the const interpreter
+        macro_rules! both {
+            ($($name:ident: { $($code:tt)* })*) => {
+                $(
+                    #[test]
+                    fn $name() {
+                        type Code = bf!(Interpreter; $($code)*);
+                        const OUT: Output<32> = run(stringify!($($code)*).as_bytes(), b"");
+                        assert_eq!(OUT.as_slice(), <GetOutput<Execute<Code>> as Debug>::data());
+                    }
+                )*
+            };
+        }
+        both! {
+            print_cells: { +.>++.<. }
+            clear_loop: { +++[-]. }
+            move_loop: { ++++[>++<-]>.<. }
+            saturates: { -.+<.>>. }
+            skipped: { [+.]+. }
+            nested: { ++[>++[>+<-]<-]>>. }
+        }
+
+        #[test]
+        fn hello() {
+            const OUT: Output<16> = run(b"+++++++++[>++++++++>+++++++++++>++++>+++++++++>+++++++++++++<<<<<-]>.>++.+++++++..+++.>----.>+++.>++++.<<<+.-----------.>>>------.<<+.", b"");
+            assert_eq!(OUT.string().unwrap(), "Hello Types!");
+        }
+        #[test]
+        fn input() {
+            const OUT: Output<4> = run(b",[+.,]", b"HAL");
+            assert_eq!(OUT.as_slice(), [73, 66, 77]);
+        }
+    }
+}
@@ -0,0 +1,198 @@
+//! the macros used from outside the crate, nothing but what a program names is imported so every
+//! path they expand to has to be absolute
+use brainfuck::{
+    bf, bf_chunked, bf_fn, bf_lang, bf_opt,
+    brainfuck::{GetOutput, Interpreter, Snapshot},
+    define_instruction,
+    indexed::{self, Debug, Get},
+    instructions::{Command, Execute},
+    numbers::{self, Nine, One, Zero},
+    operators,
+    snippets::MoveTo,
+    step::{Load, StepN},
+    TypeNamed,
+};
+
+type GetRam<T> = <T as brainfuck::brainfuck::Debug>::Ram;
+
+#[test]
+fn bf() {
+    // the stride of the scan is a number, `NextNumber` is not imported here
+    type Code = bf!(Interpreter; +>>+>>+<<<<[>>]<<<<.(MoveTo<One>)>.);
+    assert_eq!(GetOutput::<Execute<Code>>::data(), [1, 1]);
+    assert_eq!(GetRam::<Execute<Code>>::index(), 3);
+}
+
+#[test]
+fn bf_opt() {
+    // `>+<` becomes an `IncrAt` with the offset as a number
+    type Code = bf_opt!(Interpreter; +>+>++<<.);
+    assert_eq!(GetRam::<Execute<Code>>::data(), [1, 1, 2]);
+    assert_eq!(GetOutput::<Execute<Code>>::data(), [1]);
+}
+
+#[test]
+fn bf_lang() {
+    type Ram = Interpreter;
+    // the value of `a` is moved to `b` and the scratch cell after them, then back to `a`
+    assert_eq!(
+        <bf_lang!(Ram; let a = 2; let b; add b, a;)>::name(),
+        <bf!(Ram; ++[->+>+<<]>>[-<<+>>])>::name()
+    );
+    type Product = bf_lang!(Ram;
+        let a = 3;
+        let b = 4;
+        let product;
+        while a {
+            add product, b;
+            sub a, 1;
+        }
+        print product;
+        print b;
+    );
+    assert_eq!(GetOutput::<Execute<Product>>::data(), [12, 4]);
+    type Copied = bf_lang!(Ram;
+        let a = 5;
+        let b = 1;
+        copy b, a;
+        add a, 2;
+        sub a, b;
+        print a;
+        print b;
+        clear b;
+        print b;
+    );
+    assert_eq!(GetOutput::<Execute<Copied>>::data(), [2, 5, 0]);
+    // a `let` in a loop starts over in every iteration
+    type Counted = bf_lang!(Ram;
+        let n = 3;
+        while n {
+            let step = 2;
+            sub n, 1;
+            print step;
+        }
+    );
+    assert_eq!(GetOutput::<Execute<Counted>>::data(), [2, 2, 2]);
+}
+
+#[test]
+fn bf_chunked() {
+    type Ram = Execute<bf!(Interpreter; +++)>;
+    type Whole = Execute<bf!(Ram; [>+<-]>>++[<+>-]<.+.>+++.)>;
+    type Chunked = bf_chunked!(Ram; [>+<-]>>++[<+>-]<.+.>+++.);
+    assert_eq!(GetRam::<Chunked>::data(), GetRam::<Whole>::data());
+    assert_eq!(GetRam::<Chunked>::data(), [0, 6, 3]);
+    assert_eq!(GetOutput::<Chunked>::data(), GetOutput::<Whole>::data());
+    assert_eq!(GetOutput::<Chunked>::data(), [5, 6, 3]);
+}
+
+#[test]
+fn bf_chunked_without_loops() {
+    type Chunked = bf_chunked!(Interpreter; ++>+.<.);
+    assert_eq!(GetOutput::<Chunked>::data(), [1, 2]);
+    // an alias per chunk gives the same final state
+    bf_chunked!(type Aliased = Interpreter; ++>+.<.);
+    assert_eq!(Aliased::name(), Chunked::name());
+}
+
+// `$code` repeated 256 times and followed by `+.`, passed to `$bf` after `$head`
+macro_rules! repeated {
+    ($bf:ident![$($head:tt)*] $($code:tt)*) => {
+        repeated!(@x16 $bf![$($head)*] $($code)* $($code)* $($code)* $($code)* $($code)*
+            $($code)* $($code)* $($code)* $($code)* $($code)* $($code)* $($code)* $($code)*
+            $($code)* $($code)* $($code)*)
+    };
+    (@x16 $bf:ident![$($head:tt)*] $($code:tt)*) => {
+        $bf!($($head)*; $($code)* $($code)* $($code)* $($code)* $($code)* $($code)* $($code)*
+            $($code)* $($code)* $($code)* $($code)* $($code)* $($code)* $($code)* $($code)*
+            $($code)* +.)
+    };
+}
+
+#[test]
+fn bf_long_program() {
+    // 4098 commands expand in one step, far more than the macro recursion limit, but running
+    // a chain this deep is left to `bf_chunked!`
+    #[allow(dead_code)]
+    type Code = repeated!(bf![Interpreter] +-+-+-+-+-+-+-+-);
+}
+
+#[test]
+fn bf_chunked_long_program() {
+    // a single type nesting all of the chunks overflows rustc's stack, an alias per chunk does not
+    repeated!(bf_chunked![type Final = Interpreter] +-+-+-+-+-+-+-+-);
+    assert_eq!(GetOutput::<Final>::data(), [1]);
+}
+
+struct Doubling;
+type Doubled<State> = <<State as Get>::Val as numbers::Plus<<State as Get>::Val>>::Output;
+impl<State: Get> Command<State> for Doubling
+where
+    <State as Get>::Val: numbers::Plus<<State as Get>::Val>,
+    State: indexed::VecOp<operators::Set<Doubled<State>>>,
+{
+    type Out = <State as indexed::VecOp<operators::Set<Doubled<State>>>>::Indexed;
+}
+define_instruction!(
+    /// doubles the current cell
+    Double = Doubling
+);
+
+struct Swapping;
+type Cell<State, Offset> = <State as indexed::GetAt<Offset>>::Val;
+type SetFirst<State> = <State as indexed::VecOp<operators::Set<Cell<State, One>>>>::Indexed;
+impl<State> Command<State> for Swapping
+where
+    State: indexed::GetAt<Zero> + indexed::GetAt<One>,
+    State: indexed::VecOp<operators::Set<Cell<State, One>>>,
+    SetFirst<State>: indexed::VecOpAt<operators::Set<Cell<State, Zero>>, One>,
+{
+    type Out =
+        <SetFirst<State> as indexed::VecOpAt<operators::Set<Cell<State, Zero>>, One>>::Indexed;
+}
+define_instruction!(Swap = Swapping);
+
+#[test]
+fn define_instruction() {
+    type Ram = Interpreter;
+    type Code = Double<bf!(Ram; +++)>;
+    assert_eq!(
+        Code::name(),
+        "Double<Incr<Incr<Incr<Interpreter<List<0,[]>, List<0,[]>>>>>>"
+    );
+    assert_eq!(GetRam::<Execute<Code>>::data(), [6]);
+    assert_eq!(GetRam::<Code>::data(), GetRam::<Ram>::data());
+    // spliced into loops
+    type Looped = bf!(Ram; +++[>+(Doubling)<-]>.);
+    assert_eq!(GetOutput::<Execute<Looped>>::data(), [14]);
+    type Swapped = bf!(Ram; ++>+++<(Swapping).>.);
+    assert_eq!(GetOutput::<Execute<Swapped>>::data(), [3, 2]);
+    // and run one command at a time
+    type Eighteen = <Nine as numbers::Plus<Nine>>::Output;
+    type Stepped = <Load<Looped> as StepN<<Eighteen as numbers::Plus<Nine>>::Output>>::Out;
+    assert_eq!(Snapshot::of::<Stepped>(), Snapshot::of::<Execute<Looped>>());
+}
+
+#[test]
+fn bf_fn_matches_execute() {
+    type Code = bf!(Interpreter; ++++++[>++++++++<-]>+.+.>+++[>++<-]>..<<.);
+    bf_fn!(fn native; ++++++[>++++++++<-]>+.+.>+++[>++<-]>..<<.);
+
+    let expected = <GetOutput<Execute<Code>> as Debug>::data();
+    let mut output = Vec::new();
+    native(&mut std::io::empty(), &mut output).unwrap();
+    assert_eq!(output, [49, 50, 6, 6, 50]);
+    assert_eq!(
+        output.into_iter().map(i64::from).collect::<Vec<_>>(),
+        expected
+    );
+}
+
+#[test]
+fn bf_fn_input() {
+    // echo the input until a 0 byte or eof, incrementing every byte
+    bf_fn!(fn native; ,[+.,]);
+    let mut output = Vec::new();
+    native(&mut &b"HAL"[..], &mut output).unwrap();
+    assert_eq!(output, b"IBM");
+}